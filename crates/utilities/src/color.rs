@@ -0,0 +1,51 @@
+use anyhow::{bail, Result};
+use std::fmt;
+use std::str::FromStr;
+
+/// When output is colored, `auto` colors it only when stdout is a terminal
+/// and honours `NO_COLOR`, `CLICOLOR` and `CLICOLOR_FORCE`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ColorChoice {
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    /// Applies to everything colored with `colored`, including the strings
+    /// Lua extensions get from `apply_color` and `apply_style`
+    pub fn apply(self) {
+        match self {
+            ColorChoice::Auto => colored::control::unset_override(),
+            ColorChoice::Always => colored::control::set_override(true),
+            ColorChoice::Never => colored::control::set_override(false),
+        }
+    }
+}
+
+impl FromStr for ColorChoice {
+    type Err = anyhow::Error;
+
+    fn from_str(choice: &str) -> Result<Self> {
+        match choice.to_lowercase().as_str() {
+            "auto" => Ok(ColorChoice::Auto),
+            "always" => Ok(ColorChoice::Always),
+            "never" => Ok(ColorChoice::Never),
+            _ => bail!(
+                "unknown color choice '{}', use 'auto', 'always' or 'never'",
+                choice
+            ),
+        }
+    }
+}
+
+impl fmt::Display for ColorChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ColorChoice::Auto => write!(f, "auto"),
+            ColorChoice::Always => write!(f, "always"),
+            ColorChoice::Never => write!(f, "never"),
+        }
+    }
+}
@@ -1,11 +1,42 @@
 use colored::ColoredString;
 use colored::Colorize;
 use std::fmt::Display;
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use textwrap;
 use textwrap::fill;
 
+use crate::theme::{paint, Role};
+
 pub const NEWLINE: &str = "\n";
-pub const LINE_WRAP_AT: usize = 80;
+/// Line width when stdout isn't a terminal and no width is configured
+pub const DEFAULT_LINE_WIDTH: usize = 80;
+
+/// 0 while no width is configured
+static CONFIGURED_LINE_WIDTH: AtomicUsize = AtomicUsize::new(0);
+
+/// Overrides the detected terminal width, e.g. with `Config.width`
+pub fn set_line_width(width: usize) {
+    CONFIGURED_LINE_WIDTH.store(width, Ordering::Relaxed);
+}
+
+/// Width lines are wrapped at, the configured one or else the terminal width
+pub fn line_width() -> usize {
+    match CONFIGURED_LINE_WIDTH.load(Ordering::Relaxed) {
+        0 => terminal_width().unwrap_or(DEFAULT_LINE_WIDTH),
+        width => width,
+    }
+}
+
+/// Output that doesn't go to a terminal keeps the default width, so files
+/// don't depend on the size of the window they were written from
+fn terminal_width() -> Option<usize> {
+    if !std::io::stdout().is_terminal() {
+        return None;
+    }
+    let (columns, _) = crossterm::terminal::size().ok()?;
+    Some(columns as usize).filter(|&columns| columns > 0)
+}
 
 pub trait Join {
     fn join(&self, list: Vec<Self>) -> Self
@@ -52,7 +83,7 @@ pub fn indent(text: &ColoredString) -> ColoredString {
 }
 
 pub fn format_integer(number: usize) -> ColoredString {
-    number
+    let formatted = number
         .to_string()
         .as_bytes()
         .rchunks(3)
@@ -60,8 +91,8 @@ pub fn format_integer(number: usize) -> ColoredString {
         .map(std::str::from_utf8)
         .collect::<Result<Vec<&str>, _>>()
         .unwrap()
-        .join(",")
-        .yellow()
+        .join(",");
+    paint(&formatted, Role::Number)
 }
 
 pub fn horizontal_line_with_length(length: usize) -> ColoredString {
@@ -69,5 +100,5 @@ pub fn horizontal_line_with_length(length: usize) -> ColoredString {
 }
 
 pub fn horizontal_line() -> ColoredString {
-    horizontal_line_with_length(LINE_WRAP_AT)
+    horizontal_line_with_length(line_width())
 }
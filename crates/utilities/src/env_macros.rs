@@ -25,3 +25,10 @@ macro_rules! LUA_DIR {
         env!("LUA_DIR")
     };
 }
+
+#[macro_export]
+macro_rules! DICTIONARY_CACHE_DIR {
+    () => {
+        env!("DICTIONARY_CACHE_DIR")
+    };
+}
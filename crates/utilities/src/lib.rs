@@ -1,6 +1,9 @@
 pub mod anyhow_serde;
+pub mod color;
 pub mod colored_string_utils;
 pub mod env_macros;
 pub mod file_utils;
 pub mod language;
 pub mod pager;
+pub mod retry;
+pub mod theme;
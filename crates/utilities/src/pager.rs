@@ -2,11 +2,31 @@ use anyhow::Result;
 use minus::Pager;
 use std::fmt::Display;
 use std::fmt::Write;
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static PAGER_DISABLED: AtomicBool = AtomicBool::new(false);
+
+/// Makes the print functions write straight to stdout, e.g. for `--no-pager`
+pub fn disable_pager() {
+    PAGER_DISABLED.store(true, Ordering::Relaxed);
+}
+
+/// The pager is only started when a person reads stdout
+fn use_pager() -> bool {
+    !PAGER_DISABLED.load(Ordering::Relaxed) && std::io::stdout().is_terminal()
+}
 
 pub fn print_lines_in_pager<T>(entries: &[T]) -> Result<()>
 where
     T: Display,
 {
+    if !use_pager() {
+        for entry in entries {
+            println!("{}", entry);
+        }
+        return Ok(());
+    }
     let mut output = Pager::new();
 
     for entry in entries {
@@ -20,6 +40,10 @@ pub fn print_in_pager<T>(value: &T) -> Result<()>
 where
     T: Display,
 {
+    if !use_pager() {
+        println!("{}", value);
+        return Ok(());
+    }
     let mut output = Pager::new();
     writeln!(output, "{}", value)?;
     minus::page_all(output)?;
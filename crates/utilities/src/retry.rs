@@ -0,0 +1,54 @@
+use anyhow::Result;
+use std::future::Future;
+use std::time::Duration;
+
+/// How long to wait for a backend and how often to try again
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct RetryPolicy {
+    /// Time a single call may take
+    pub timeout: Duration,
+    /// Number of additional attempts after the first one failed
+    pub retries: u32,
+    /// Delay before the first retry, doubled for every further retry
+    pub backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(10),
+            retries: 2,
+            backoff: Duration::from_millis(250),
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn backoff_for(&self, attempt: u32) -> Duration {
+        self.backoff.saturating_mul(2u32.saturating_pow(attempt))
+    }
+
+    /// Runs the operation again, after a growing delay, as long as it fails
+    /// with an error the predicate considers transient. Waits for the backoff
+    /// without blocking the runtime.
+    pub async fn retry_async<T, F, Fut>(
+        &self,
+        is_transient: impl Fn(&anyhow::Error) -> bool,
+        mut operation: F,
+    ) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let mut attempt = 0;
+        loop {
+            match operation().await {
+                Err(err) if attempt < self.retries && is_transient(&err) => {
+                    tokio::time::sleep(self.backoff_for(attempt)).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
@@ -0,0 +1,221 @@
+use anyhow::{bail, Result};
+use colored::{Color, ColoredString, Colorize};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::{LazyLock, RwLock};
+
+/// Parts of the output whose color is set by the theme
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Role {
+    Headword,
+    Pos,
+    SectionTitle,
+    ExampleIndex,
+    DidYouMean,
+    Number,
+    Form,
+    NotFound,
+}
+
+impl Role {
+    pub fn iterator() -> impl Iterator<Item = Role> {
+        [
+            Role::Headword,
+            Role::Pos,
+            Role::SectionTitle,
+            Role::ExampleIndex,
+            Role::DidYouMean,
+            Role::Number,
+            Role::Form,
+            Role::NotFound,
+        ]
+        .iter()
+        .copied()
+    }
+
+    /// Key of the role in `Config.theme`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Role::Headword => "headword",
+            Role::Pos => "pos",
+            Role::SectionTitle => "section_title",
+            Role::ExampleIndex => "example_index",
+            Role::DidYouMean => "did_you_mean",
+            Role::Number => "number",
+            Role::Form => "form",
+            Role::NotFound => "not_found",
+        }
+    }
+}
+
+impl FromStr for Role {
+    type Err = anyhow::Error;
+
+    fn from_str(role: &str) -> Result<Self> {
+        match Role::iterator().find(|r| r.name() == role) {
+            Some(role) => Ok(role),
+            None => bail!(
+                "unknown theme role '{}', use one of: {}",
+                role,
+                Role::iterator()
+                    .map(|r| r.name())
+                    .collect::<Vec<&str>>()
+                    .join(", ")
+            ),
+        }
+    }
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Colors of the roles, a role without a color is printed as is
+#[derive(Clone, Debug, PartialEq)]
+pub struct Theme {
+    colors: [Option<Color>; 8],
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::dark()
+    }
+}
+
+impl Theme {
+    /// For terminals with a dark background, the default. It keeps the colors
+    /// the output had before themes, other colors are opted in to per role.
+    pub fn dark() -> Self {
+        Theme {
+            colors: [
+                Some(Color::Green),
+                None,
+                None,
+                None,
+                Some(Color::Yellow),
+                Some(Color::Yellow),
+                Some(Color::Yellow),
+                Some(Color::Red),
+            ],
+        }
+    }
+
+    /// For terminals with a light background
+    pub fn light() -> Self {
+        Theme {
+            colors: [
+                Some(Color::TrueColor {
+                    r: 0x00,
+                    g: 0x5f,
+                    b: 0x87,
+                }),
+                Some(Color::Blue),
+                Some(Color::Black),
+                Some(Color::AnsiColor(242)),
+                Some(Color::TrueColor {
+                    r: 0xaf,
+                    g: 0x5f,
+                    b: 0x00,
+                }),
+                Some(Color::TrueColor {
+                    r: 0xaf,
+                    g: 0x5f,
+                    b: 0x00,
+                }),
+                Some(Color::TrueColor {
+                    r: 0xaf,
+                    g: 0x5f,
+                    b: 0x00,
+                }),
+                Some(Color::Red),
+            ],
+        }
+    }
+
+    pub fn preset(name: &str) -> Result<Self> {
+        match name.to_lowercase().as_str() {
+            "dark" => Ok(Theme::dark()),
+            "light" => Ok(Theme::light()),
+            _ => bail!("unknown theme '{}', use 'dark' or 'light'", name),
+        }
+    }
+
+    pub fn color(&self, role: Role) -> Option<Color> {
+        self.colors[role as usize]
+    }
+
+    /// Sets the color of a role as accepted by [`parse_color`], `none` removes it
+    pub fn set_color(&mut self, role: Role, color: &str) -> Result<()> {
+        self.colors[role as usize] = match color {
+            "none" => None,
+            color => Some(parse_color(color)?),
+        };
+        Ok(())
+    }
+
+    pub fn paint(&self, text: &str, role: Role) -> ColoredString {
+        match self.color(role) {
+            Some(color) => text.color(color),
+            None => text.normal(),
+        }
+    }
+}
+
+/// Parses a color name like `red` or `bright_red`, a number of the 256 color
+/// palette like `208` or a truecolor like `#ff8700`. Truecolors are turned
+/// into the closest color unless `COLORTERM` tells they are supported.
+pub fn parse_color(color: &str) -> Result<Color> {
+    if let Some(hex) = color.strip_prefix('#') {
+        let channel = |i: usize| {
+            hex.get(i..i + 2)
+                .and_then(|channel| u8::from_str_radix(channel, 16).ok())
+        };
+        return match (hex.len(), channel(0), channel(2), channel(4)) {
+            (6, Some(r), Some(g), Some(b)) => Ok(Color::TrueColor { r, g, b }),
+            _ => bail!("'{}' isn't a color like #ff8700", color),
+        };
+    }
+    if color.chars().all(|c| c.is_ascii_digit()) && !color.is_empty() {
+        return match color.parse::<u8>() {
+            Ok(code) => Ok(Color::AnsiColor(code)),
+            Err(_) => bail!("'{}' isn't a color of the 256 color palette", color),
+        };
+    }
+    let color = match color.to_lowercase().replace(' ', "_").as_str() {
+        "black" => Color::Black,
+        "red" => Color::Red,
+        "green" => Color::Green,
+        "yellow" => Color::Yellow,
+        "blue" => Color::Blue,
+        "magenta" => Color::Magenta,
+        "cyan" => Color::Cyan,
+        "white" => Color::White,
+        "bright_black" => Color::BrightBlack,
+        "bright_red" => Color::BrightRed,
+        "bright_green" => Color::BrightGreen,
+        "bright_yellow" => Color::BrightYellow,
+        "bright_blue" => Color::BrightBlue,
+        "bright_magenta" => Color::BrightMagenta,
+        "bright_cyan" => Color::BrightCyan,
+        "bright_white" => Color::BrightWhite,
+        _ => bail!("unknown color '{}'", color),
+    };
+    Ok(color)
+}
+
+static THEME: LazyLock<RwLock<Theme>> = LazyLock::new(Default::default);
+
+/// Makes the theme the one used by [`paint`], e.g. the one of `Config.theme`
+pub fn set_theme(theme: Theme) {
+    *THEME.write().unwrap_or_else(|err| err.into_inner()) = theme;
+}
+
+/// Colors the text like the current theme colors the role
+pub fn paint(text: &str, role: Role) -> ColoredString {
+    THEME
+        .read()
+        .unwrap_or_else(|err| err.into_inner())
+        .paint(text, role)
+}
@@ -0,0 +1,101 @@
+use anyhow::Result;
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+use std::io::Write;
+
+use utilities::language::Language;
+use wiktionary_en_db::client::{DbClient, Document};
+use wiktionary_en_entities::anki::{field, guid, unix_time_of_date, CardFields, CardTemplates};
+use wiktionary_en_entities::dictionary_entry::DictionaryEntry;
+
+/// Collection the history extension keeps looked up words in
+const HISTORY_EXTENSION: &str = "history";
+
+/// Which history words become cards
+pub struct HistoryFilter {
+    pub min_count: Option<u64>,
+    /// Only words looked up on this day or later, as `YYYY-MM-DD`
+    pub since: Option<String>,
+}
+
+impl HistoryFilter {
+    fn selector(&self) -> Result<Value> {
+        let mut selector = Map::new();
+        selector.insert("word".to_string(), json!({ "$exists": true }));
+        if let Some(min_count) = self.min_count {
+            selector.insert("count".to_string(), json!({ "$gte": min_count }));
+        }
+        if let Some(since) = &self.since {
+            selector.insert(
+                "now_seen_at".to_string(),
+                json!({ "$gte": unix_time_of_date(since)? }),
+            );
+        }
+        Ok(Value::Object(selector))
+    }
+}
+
+pub struct AnkiExport {
+    pub cards: usize,
+    /// History words without an entry in the dictionary
+    pub skipped: Vec<String>,
+}
+
+/// Writes the history words as notes of a tab separated Anki import file.
+/// The file sets a GUID per word, so importing it again updates the notes
+/// of words that were exported before.
+pub async fn export(
+    client: &DbClient,
+    language: Language,
+    filter: &HistoryFilter,
+    templates: &CardTemplates,
+    deck: &str,
+    writer: &mut impl Write,
+) -> Result<AnkiExport> {
+    let history = client
+        .find_in_extension_collection(HISTORY_EXTENSION, Document::from(filter.selector()?), None)
+        .await?
+        .documents;
+    writeln!(writer, "#separator:tab")?;
+    writeln!(writer, "#html:true")?;
+    writeln!(writer, "#notetype:Basic")?;
+    writeln!(writer, "#deck:{}", field(deck))?;
+    writeln!(writer, "#guid column:1")?;
+    let words: Vec<String> = history
+        .iter()
+        .filter_map(|history_entry| history_entry.document["word"].as_str())
+        .map(str::to_string)
+        .collect();
+    let mut entries_of_word: HashMap<String, Vec<DictionaryEntry>> = HashMap::new();
+    for entry in client.find_by_words(&words).await? {
+        entries_of_word
+            .entry(entry.word.clone())
+            .or_default()
+            .push(entry);
+    }
+    let mut export = AnkiExport {
+        cards: 0,
+        skipped: Vec::new(),
+    };
+    for history_entry in history {
+        let history_entry = history_entry.document;
+        let Some(word) = history_entry["word"].as_str() else {
+            continue;
+        };
+        let Some(entries) = entries_of_word.get(word) else {
+            export.skipped.push(word.to_string());
+            continue;
+        };
+        let count = history_entry["count"].as_u64().unwrap_or_default();
+        let fields = CardFields::of(word, count, entries);
+        writeln!(
+            writer,
+            "{}\t{}\t{}",
+            guid(language, word),
+            field(&fields.fill(&templates.front)),
+            field(&fields.fill(&templates.back))
+        )?;
+        export.cards += 1;
+    }
+    Ok(export)
+}
@@ -0,0 +1,72 @@
+use anyhow::{bail, Result};
+use colored::ColoredString;
+use colored::Colorize;
+use std::fmt;
+
+use utilities::colored_string_utils::*;
+use utilities::theme::{paint, Role};
+use wiktionary_en_db::client::{CategoryCount, DbClient};
+
+/// Categories matching a search, or the headwords of a category
+pub struct Categories {
+    title: String,
+    summary: String,
+    counts: Vec<CategoryCount>,
+}
+
+impl fmt::Display for Categories {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.to_pretty_string())
+    }
+}
+
+impl Categories {
+    /// Categories containing the term, the biggest first
+    pub async fn search(client: &DbClient, term: &str) -> Result<Self> {
+        let mut counts = client.search_categories(term).await?;
+        if counts.is_empty() {
+            bail!("no categories found for '{}'", term);
+        }
+        counts.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.name.cmp(&b.name)));
+        Ok(Self {
+            title: format!("Categories matching '{}'", term),
+            summary: format!("{} categories", format_integer(counts.len())),
+            counts,
+        })
+    }
+
+    /// Headwords of the category in alphabetical order
+    pub async fn members(client: &DbClient, category: &str) -> Result<Self> {
+        let counts = client.category_members(category).await?;
+        if counts.is_empty() {
+            bail!(
+                "no entries found in the category '{}', try 'categories --search'",
+                category
+            );
+        }
+        let entries: u64 = counts.iter().map(|count| count.count).sum();
+        Ok(Self {
+            title: category.to_string(),
+            summary: format!("{} entries", format_integer(entries as usize)),
+            counts,
+        })
+    }
+
+    fn to_pretty_string(&self) -> ColoredString {
+        let horizontal_line = horizontal_line();
+        let mut res: Vec<ColoredString> = vec![
+            horizontal_line.clone(),
+            format!(
+                "{} ({})",
+                paint(&self.title, Role::Headword).bold(),
+                self.summary
+            )
+            .normal(),
+            horizontal_line,
+        ];
+        for count in &self.counts {
+            res.push(format!(" {} {}", count.name, format!("({})", count.count).dimmed()).normal());
+        }
+        NEWLINE.normal().join(res)
+    }
+}
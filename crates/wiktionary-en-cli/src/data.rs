@@ -0,0 +1,109 @@
+use anyhow::{bail, Context, Result};
+use colored::ColoredString;
+use colored::Colorize;
+use futures_util::StreamExt;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::{BufRead, Write};
+use std::pin::pin;
+
+use utilities::colored_string_utils::*;
+use wiktionary_en_db::client::{DbClient, ImportMode, ImportResult};
+
+pub async fn export(
+    client: &DbClient,
+    extension_name: Option<String>,
+    writer: &mut impl Write,
+) -> Result<()> {
+    let extension_names = match extension_name {
+        Some(extension_name) => vec![extension_name],
+        None => client.list_extension_collections().await?,
+    };
+    for extension_name in extension_names {
+        let mut documents = pin!(client.export_extension_collection(&extension_name).await?);
+        while let Some(document) = documents.next().await {
+            let line = json!({"extension": extension_name, "document": document?});
+            writeln!(writer, "{}", line)?;
+        }
+    }
+    Ok(())
+}
+
+pub struct ImportReport {
+    mode: ImportMode,
+    results: Vec<(String, ImportResult)>,
+}
+
+impl fmt::Display for ImportReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.to_pretty_string())
+    }
+}
+
+impl ImportReport {
+    fn to_pretty_string(&self) -> ColoredString {
+        let mut res: Vec<ColoredString> = Vec::new();
+        res.push(format!("{} {}", "Import mode:".bold(), self.mode).normal());
+        for (extension_name, result) in &self.results {
+            res.push(
+                format!(
+                    "{:<19}: {} imported, {} skipped, {} failed",
+                    extension_name.green(),
+                    format_integer(result.imported),
+                    format_integer(result.skipped),
+                    format_integer(result.failures.len())
+                )
+                .normal(),
+            );
+            for failure in &result.failures {
+                res.push(format!("  {} {}", failure.id.red(), failure.error).normal());
+            }
+        }
+        NEWLINE.normal().join(res)
+    }
+}
+
+pub async fn import(
+    client: &DbClient,
+    reader: impl BufRead,
+    extension_name: Option<String>,
+    mode: ImportMode,
+) -> Result<ImportReport> {
+    let mut collections: BTreeMap<String, Vec<Value>> = BTreeMap::new();
+    for (i, line) in reader.lines().enumerate() {
+        let line = line.with_context(|| format!("Couldn't read line {} of the backup.", i))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let (extension, document) = parse_line(&line)
+            .with_context(|| format!("Couldn't parse line {} of the backup.", i))?;
+        if extension_name
+            .as_ref()
+            .is_none_or(|name| *name == extension)
+        {
+            collections.entry(extension).or_default().push(document);
+        }
+    }
+
+    let mut results = Vec::new();
+    for (extension, documents) in collections {
+        let result = client
+            .import_into_extension_collection(&extension, documents, &mode)
+            .await?;
+        results.push((extension, result));
+    }
+    Ok(ImportReport { mode, results })
+}
+
+fn parse_line(line: &str) -> Result<(String, Value)> {
+    let mut value: Value = serde_json::from_str(line)?;
+    let Some(extension) = value["extension"].as_str().map(|name| name.to_string()) else {
+        bail!("a field 'extension' must be supplied")
+    };
+    let document = value["document"].take();
+    if !document.is_object() {
+        bail!("a field 'document' must be supplied")
+    }
+    Ok((extension, document))
+}
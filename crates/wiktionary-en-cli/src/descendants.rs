@@ -0,0 +1,170 @@
+use anyhow::{bail, Result};
+use colored::ColoredString;
+use colored::Colorize;
+use std::fmt;
+
+use utilities::colored_string_utils::*;
+use utilities::language::Language;
+use utilities::retry::RetryPolicy;
+use utilities::theme::{paint, Role};
+use wiktionary_en_db::client::DbClient;
+use wiktionary_en_entities::dictionary_entry::{Descendant, DictionaryEntry};
+
+use crate::dictionaries::{first_gloss, Dictionaries};
+
+/// Descendants of an entry are looked up in other dictionaries at most this many times
+const MAX_DESCENDANT_LOOKUPS: usize = 10;
+
+struct LinkedWord {
+    word: String,
+    /// Set when the word was found in the dictionary of its language
+    found_in: Option<Language>,
+    gloss: Option<String>,
+}
+
+struct DescendantNode {
+    depth: u32,
+    language: Option<String>,
+    /// Shown when the words can't be told from the templates
+    text: String,
+    words: Vec<LinkedWord>,
+    borrowed: bool,
+}
+
+struct EntryDescendants {
+    pos: String,
+    etymology_number: Option<u32>,
+    nodes: Vec<DescendantNode>,
+}
+
+pub struct DescendantTree {
+    word: String,
+    entries: Vec<EntryDescendants>,
+}
+
+impl fmt::Display for DescendantTree {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.to_pretty_string())
+    }
+}
+
+impl DescendantTree {
+    /// Builds the descendants tree of every entry of the word. Descendants in
+    /// the language of an imported dictionary are looked up there.
+    pub async fn lookup(client: &DbClient, word: &str, retry_policy: RetryPolicy) -> Result<Self> {
+        let hits = client.find_all_by_word(word).await?;
+        if hits.is_empty() {
+            bail!("no entries found for '{}'", word);
+        }
+        let dictionaries = Dictionaries::imported(client, retry_policy).await?;
+        let mut entries = Vec::new();
+        for hit in &hits {
+            entries.push(EntryDescendants {
+                pos: hit.pos.clone(),
+                etymology_number: hit.etymology_number,
+                nodes: descendants_of(&dictionaries, hit).await?,
+            });
+        }
+        Ok(Self {
+            word: word.to_string(),
+            entries,
+        })
+    }
+
+    fn to_pretty_string(&self) -> ColoredString {
+        let horizontal_line = horizontal_line();
+        let mut res: Vec<ColoredString> = Vec::new();
+        for entry in &self.entries {
+            res.push(horizontal_line.clone());
+            let etymology_number = entry
+                .etymology_number
+                .map(|number| format!(", etymology {}", number))
+                .unwrap_or_default();
+            res.push(
+                format!(
+                    "{} ({}{})",
+                    paint(&self.word, Role::Headword).bold(),
+                    entry.pos,
+                    etymology_number
+                )
+                .normal(),
+            );
+            res.push(horizontal_line.clone());
+            if entry.nodes.is_empty() {
+                res.push("No descendants known".italic());
+            }
+            for node in &entry.nodes {
+                res.push(format_node(node));
+            }
+        }
+        NEWLINE.normal().join(res)
+    }
+}
+
+async fn descendants_of(
+    dictionaries: &Dictionaries,
+    entry: &DictionaryEntry,
+) -> Result<Vec<DescendantNode>> {
+    let mut lookups = 0;
+    let mut nodes = Vec::new();
+    for descendant in &entry.descendants {
+        let mut words = Vec::new();
+        for word in descendant.words() {
+            let found = match descendant.language_code() {
+                Some(language_code)
+                    if lookups < MAX_DESCENDANT_LOOKUPS && dictionaries.covers(language_code) =>
+                {
+                    // words that aren't found cost a query as well
+                    lookups += 1;
+                    dictionaries.find(language_code, word).await?
+                }
+                _ => None,
+            };
+            words.push(LinkedWord {
+                word: word.to_string(),
+                found_in: found.as_ref().map(|(language, _)| *language),
+                gloss: found.as_ref().and_then(|(_, found)| first_gloss(found)),
+            });
+        }
+        nodes.push(node_of(descendant, words));
+    }
+    Ok(nodes)
+}
+
+fn node_of(descendant: &Descendant, words: Vec<LinkedWord>) -> DescendantNode {
+    DescendantNode {
+        depth: descendant.depth,
+        language: descendant.language().map(String::from),
+        text: descendant.text.clone(),
+        words,
+        borrowed: descendant.is_borrowing(),
+    }
+}
+
+fn format_node(node: &DescendantNode) -> ColoredString {
+    let mut line = format!(
+        "{}└ {}",
+        "  ".repeat(node.depth.saturating_sub(1) as usize),
+        if node.borrowed { "→ " } else { "" }
+    );
+    match &node.language {
+        Some(language) if !node.words.is_empty() => {
+            line.push_str(&language.italic().to_string());
+            let words: Vec<String> = node.words.iter().map(format_word).collect();
+            line.push_str(&format!(" {}", words.join(", ")));
+        }
+        _ => line.push_str(node.text.trim_start_matches(['→', ' '])),
+    }
+    line.normal()
+}
+
+fn format_word(word: &LinkedWord) -> String {
+    let mut formatted = word.word.yellow().to_string();
+    if let Some(language) = word.found_in {
+        formatted.push_str(&format!(" [{}]", language).dimmed().to_string());
+    }
+    if let Some(gloss) = &word.gloss {
+        formatted.push_str(&format!(" “{}”", gloss));
+    }
+    formatted
+}
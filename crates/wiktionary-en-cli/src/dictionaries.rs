@@ -0,0 +1,64 @@
+use anyhow::Result;
+use std::str::FromStr;
+
+use utilities::language::Language;
+use utilities::retry::RetryPolicy;
+use wiktionary_en_db::client::{DbClient, Pagination};
+use wiktionary_en_entities::dictionary_entry::DictionaryEntry;
+
+/// Clients of the imported dictionaries words of other languages can be looked up in
+pub struct Dictionaries {
+    clients: Vec<(Language, DbClient)>,
+}
+
+impl Dictionaries {
+    pub async fn imported(client: &DbClient, retry_policy: RetryPolicy) -> Result<Self> {
+        let databases = client.list_databases().await?;
+        let mut clients = Vec::new();
+        for language in Language::iterator() {
+            if databases.contains(&language.to_string()) {
+                clients.push((
+                    language,
+                    DbClient::connect_with_retry_policy(language, retry_policy)?,
+                ));
+            }
+        }
+        Ok(Self { clients })
+    }
+
+    fn client_of(&self, language_code: &str) -> Option<&(Language, DbClient)> {
+        let language = Language::from_str(language_code).ok()?;
+        self.clients
+            .iter()
+            .find(|(imported, _)| *imported == language)
+    }
+
+    /// Whether looking up a word of the language queries a dictionary
+    pub fn covers(&self, language_code: &str) -> bool {
+        self.client_of(language_code).is_some()
+    }
+
+    /// The first entry of the word in the dictionary of the language, `None`
+    /// if the language has no imported dictionary
+    pub async fn find(
+        &self,
+        language_code: &str,
+        word: &str,
+    ) -> Result<Option<(Language, DictionaryEntry)>> {
+        let Some((language, client)) = self.client_of(language_code) else {
+            return Ok(None);
+        };
+        let page = client.find_by_word(word, &Pagination::page(1, 1)).await?;
+        Ok(page.hits.into_iter().next().map(|hit| (*language, hit)))
+    }
+}
+
+/// The first gloss of an entry, to tell what a word found elsewhere means
+pub fn first_gloss(entry: &DictionaryEntry) -> Option<String> {
+    entry
+        .senses
+        .iter()
+        .flat_map(|sense| sense.glosses.first())
+        .next()
+        .cloned()
+}
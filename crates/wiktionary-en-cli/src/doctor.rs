@@ -0,0 +1,349 @@
+use colored::ColoredString;
+use colored::Colorize;
+use std::fmt;
+use std::io::BufRead;
+use std::path::Path;
+
+use utilities::colored_string_utils::*;
+use utilities::file_utils::get_file_reader;
+use utilities::language::Language;
+#[cfg(feature = "sonic")]
+use utilities::retry::RetryPolicy;
+use utilities::{DICTIONARY_CONFIG, DICTIONARY_EXTENSIONS, LUA_DIR};
+use wiktionary_en_db::client::{DbClient, DbClientMutex};
+use wiktionary_en_entities::config::Config;
+use wiktionary_en_entities::dictionary_entry::DictionaryEntry;
+use wiktionary_en_lua::config::ConfigHandler;
+use wiktionary_en_lua::extension::ExtensionHandler;
+
+enum CheckStatus {
+    Passed,
+    Failed,
+    Skipped,
+}
+
+struct Check {
+    name: String,
+    status: CheckStatus,
+    detail: String,
+    fix: Option<String>,
+}
+
+impl Check {
+    fn passed(name: &str, detail: String) -> Self {
+        Self {
+            name: name.to_string(),
+            status: CheckStatus::Passed,
+            detail,
+            fix: None,
+        }
+    }
+
+    fn failed(name: &str, detail: String, fix: String) -> Self {
+        Self {
+            name: name.to_string(),
+            status: CheckStatus::Failed,
+            detail,
+            fix: Some(fix),
+        }
+    }
+
+    fn skipped(name: &str, reason: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            status: CheckStatus::Skipped,
+            detail: reason.to_string(),
+            fix: None,
+        }
+    }
+
+    fn to_pretty_string(&self) -> ColoredString {
+        let mark = match self.status {
+            CheckStatus::Passed => "✔".green(),
+            CheckStatus::Failed => "✘".red(),
+            CheckStatus::Skipped => "-".dimmed(),
+        };
+        let mut res: Vec<ColoredString> =
+            vec![format!("{} {:<24} {}", mark, self.name.bold(), self.detail).normal()];
+        if let Some(fix) = &self.fix {
+            res.push(format!("  {} {}", "fix:".yellow(), fix).normal());
+        }
+        NEWLINE.normal().join(res)
+    }
+}
+
+pub struct Doctor {
+    language: Language,
+    checks: Vec<Check>,
+}
+
+impl fmt::Display for Doctor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.to_pretty_string())
+    }
+}
+
+impl Doctor {
+    pub async fn examine(language: Option<Language>, db_path: Option<String>) -> Self {
+        let (config_check, config) = check_config();
+        let language = config.or_use_config_or_default(language);
+        let mut checks = vec![config_check];
+        checks.push(check_extensions(language).await);
+        checks.extend(check_couchdb(language).await);
+        #[cfg(feature = "sonic")]
+        checks.extend(check_sonic(language, &config.retry_policy).await);
+        let db_path = utilities::file_utils::get_db_path(db_path, &language);
+        checks.push(check_extract(&db_path, language));
+        Self { language, checks }
+    }
+
+    pub fn failures(&self) -> usize {
+        self.checks
+            .iter()
+            .filter(|check| matches!(check.status, CheckStatus::Failed))
+            .count()
+    }
+
+    fn to_pretty_string(&self) -> ColoredString {
+        let mut res: Vec<ColoredString> = vec![format!(
+            "{} {}",
+            "Checking the setup for language".bold(),
+            self.language.to_string().green().bold()
+        )
+        .normal()];
+        for check in &self.checks {
+            res.push(check.to_pretty_string());
+        }
+        res.push(match self.failures() {
+            0 => "all checks passed".green(),
+            failures => format!("{} of {} checks failed", failures, self.checks.len()).red(),
+        });
+        NEWLINE.normal().join(res)
+    }
+}
+
+fn check_config() -> (Check, Config) {
+    let name = "lua config";
+    match ConfigHandler::init() {
+        Ok(config_handler) => (
+            Check::passed(name, DICTIONARY_CONFIG!().to_string()),
+            config_handler.config,
+        ),
+        Err(err) => (
+            Check::failed(
+                name,
+                format!("{:#}", err),
+                format!("fix the syntax of '{}'", DICTIONARY_CONFIG!()),
+            ),
+            Config::default(),
+        ),
+    }
+}
+
+async fn check_extensions(language: Language) -> Check {
+    let name = "lua extensions";
+    let fix = format!(
+        "fix '{}' and the modules in '{}'",
+        DICTIONARY_EXTENSIONS!(),
+        LUA_DIR!()
+    );
+    // loading the extensions doesn't need a running CouchDB
+    let db_client = match DbClient::connect(language) {
+        Ok(db_client) => db_client,
+        Err(err) => return Check::failed(name, format!("{:#}", err), fix),
+    };
+    let extension_handler = match ExtensionHandler::init(DbClientMutex::from(db_client)).await {
+        Ok(extension_handler) => extension_handler,
+        Err(err) => return Check::failed(name, format!("{:#}", err), fix),
+    };
+    match (extension_handler.hooks(), extension_handler.extensions()) {
+        (Ok(hooks), Ok(extensions)) => Check::passed(
+            name,
+            format!(
+                "hooks: [{}], extensions: [{}]",
+                hooks.join(", "),
+                extensions.join(", ")
+            ),
+        ),
+        (Err(err), _) | (_, Err(err)) => Check::failed(name, format!("{:#}", err), fix),
+    }
+}
+
+async fn check_couchdb(language: Language) -> Vec<Check> {
+    let names = [
+        "couchdb reachable",
+        "couchdb authentication",
+        "couchdb databases",
+        "couchdb word-index",
+        "couchdb analytics",
+    ];
+    let mut checks: Vec<Check> = Vec::new();
+    let skip_remaining = |checks: &mut Vec<Check>, reason: &str| {
+        for name in &names[checks.len()..] {
+            checks.push(Check::skipped(name, reason));
+        }
+    };
+    let import_fix = format!(
+        "import the extract with 'wiktionary-en-import -l {}'",
+        language
+    );
+
+    let db_client = match DbClient::connect(language) {
+        Ok(db_client) => db_client,
+        Err(err) => {
+            checks.push(Check::failed(
+                names[0],
+                format!("{:#}", err),
+                format!("check COUCH_DB_HOST ({})", DbClient::host()),
+            ));
+            skip_remaining(&mut checks, "couchdb is not reachable");
+            return checks;
+        }
+    };
+    match db_client.server_version().await {
+        Ok(version) => checks.push(Check::passed(
+            names[0],
+            format!("version {} at {}", version, DbClient::host()),
+        )),
+        Err(err) => {
+            checks.push(Check::failed(
+                names[0],
+                format!("{:#}", err),
+                format!(
+                    "start it with 'just start-background-services' or check COUCH_DB_HOST ({})",
+                    DbClient::host()
+                ),
+            ));
+            skip_remaining(&mut checks, "couchdb is not reachable");
+            return checks;
+        }
+    }
+
+    let databases = match db_client.list_databases().await {
+        Ok(databases) => {
+            checks.push(Check::passed(names[1], "credentials accepted".to_string()));
+            databases
+        }
+        Err(err) => {
+            checks.push(Check::failed(
+                names[1],
+                format!("{:#}", err),
+                "check COUCH_DB_USER and COUCH_DB_PASSWORD in wiktionary-en.env and reinstall"
+                    .to_string(),
+            ));
+            skip_remaining(&mut checks, "couchdb rejected the credentials");
+            return checks;
+        }
+    };
+
+    let imported: Vec<String> = Language::iterator()
+        .map(|language| language.to_string())
+        .filter(|language| databases.contains(language))
+        .collect();
+    if !imported.contains(&db_client.database_name().to_string()) {
+        checks.push(Check::failed(
+            names[2],
+            format!(
+                "no database for '{}', imported: [{}]",
+                language,
+                imported.join(", ")
+            ),
+            import_fix,
+        ));
+        skip_remaining(&mut checks, "the database doesn't exist");
+        return checks;
+    }
+    checks.push(Check::passed(
+        names[2],
+        format!("imported: [{}]", imported.join(", ")),
+    ));
+
+    let reimport_fix = format!("{} --force", import_fix);
+    checks.push(match db_client.has_index_on_word().await {
+        Ok(true) => Check::passed(names[3], "present".to_string()),
+        Ok(false) => Check::failed(names[3], "missing".to_string(), reimport_fix.clone()),
+        Err(err) => Check::failed(names[3], format!("{:#}", err), reimport_fix.clone()),
+    });
+    checks.push(match db_client.has_analytics().await {
+        Ok(true) => match db_client.word_document_count().await {
+            Ok(count) => Check::passed(
+                names[4],
+                format!("{} entries", format_integer(count as usize)),
+            ),
+            Err(err) => Check::failed(names[4], format!("{:#}", err), reimport_fix),
+        },
+        Ok(false) => Check::failed(names[4], "missing".to_string(), reimport_fix),
+        Err(err) => Check::failed(names[4], format!("{:#}", err), reimport_fix),
+    });
+    checks
+}
+
+#[cfg(feature = "sonic")]
+async fn check_sonic(language: Language, retry_policy: &RetryPolicy) -> Vec<Check> {
+    let names = ["sonic reachable", "sonic bucket"];
+    match wiktionary_en_identifier_index::statistics(&language, retry_policy).await {
+        Ok(statistics) => {
+            let bucket = if statistics.objects > 0 {
+                Check::passed(
+                    names[1],
+                    format!("{} words", format_integer(statistics.objects as usize)),
+                )
+            } else {
+                Check::failed(
+                    names[1],
+                    format!("no words indexed for '{}'", language),
+                    format!(
+                        "create the indices with 'wiktionary-en-import -l {} --create-index'",
+                        language
+                    ),
+                )
+            };
+            vec![
+                Check::passed(
+                    names[0],
+                    format!(
+                        "{} buckets at {}",
+                        statistics.buckets,
+                        wiktionary_en_identifier_index::host()
+                    ),
+                ),
+                bucket,
+            ]
+        }
+        Err(err) => vec![
+            Check::failed(
+                names[0],
+                format!("{:#}", err),
+                format!(
+                    "start it with 'just start-background-services' or check SONIC_HOST ({}) and SONIC_PASSWORD",
+                    wiktionary_en_identifier_index::host()
+                ),
+            ),
+            Check::skipped(names[1], "sonic is not reachable"),
+        ],
+    }
+}
+
+fn check_extract(db_path: &Path, language: Language) -> Check {
+    let name = "extract file";
+    let fix = format!(
+        "download it with 'wiktionary-en-import -l {} --download' or pass --db-path",
+        language
+    );
+    let first_line = get_file_reader(db_path).and_then(|mut reader| {
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+        Ok(line)
+    });
+    match first_line {
+        Ok(line) => match line.parse::<DictionaryEntry>() {
+            Ok(_) => Check::passed(name, db_path.display().to_string()),
+            Err(err) => Check::failed(
+                name,
+                format!("'{}' isn't an extract: {:#}", db_path.display(), err),
+                fix,
+            ),
+        },
+        Err(err) => Check::failed(name, format!("{:#}", err), fix),
+    }
+}
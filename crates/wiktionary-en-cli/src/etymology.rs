@@ -0,0 +1,162 @@
+use anyhow::{bail, Result};
+use colored::ColoredString;
+use colored::Colorize;
+use std::fmt;
+
+use utilities::colored_string_utils::*;
+use utilities::language::Language;
+use utilities::retry::RetryPolicy;
+use utilities::theme::{paint, Role};
+use wiktionary_en_db::client::DbClient;
+use wiktionary_en_entities::dictionary_entry::{Ancestor, DictionaryEntry};
+
+use crate::dictionaries::{first_gloss, Dictionaries};
+
+/// Ancestors of an entry are looked up in other dictionaries at most this many times
+const MAX_ANCESTOR_LOOKUPS: usize = 5;
+
+struct AncestorNode {
+    ancestor: Ancestor,
+    /// Set when the ancestor was found in the dictionary of its language
+    found_in: Option<Language>,
+    gloss: Option<String>,
+}
+
+struct EntryAncestry {
+    pos: String,
+    etymology_number: Option<u32>,
+    ancestors: Vec<AncestorNode>,
+}
+
+pub struct EtymologyTree {
+    word: String,
+    lang_code: String,
+    entries: Vec<EntryAncestry>,
+}
+
+impl fmt::Display for EtymologyTree {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.to_pretty_string())
+    }
+}
+
+impl EtymologyTree {
+    /// Builds the ancestry of every entry of the word from its etymology
+    /// templates. Ancestors in the language of an imported dictionary are
+    /// looked up there, and the oldest one found continues the chain with
+    /// its own etymology.
+    pub async fn lookup(client: &DbClient, word: &str, retry_policy: RetryPolicy) -> Result<Self> {
+        let hits = client.find_all_by_word(word).await?;
+        let Some(first_hit) = hits.first() else {
+            bail!("no entries found for '{}'", word);
+        };
+        let lang_code = first_hit.lang_code.clone();
+        let dictionaries = Dictionaries::imported(client, retry_policy).await?;
+        let mut entries = Vec::new();
+        for hit in &hits {
+            entries.push(EntryAncestry {
+                pos: hit.pos.clone(),
+                etymology_number: hit.etymology_number,
+                ancestors: ancestors_of(&dictionaries, hit).await?,
+            });
+        }
+        Ok(Self {
+            word: word.to_string(),
+            lang_code,
+            entries,
+        })
+    }
+
+    fn to_pretty_string(&self) -> ColoredString {
+        let horizontal_line = horizontal_line();
+        let mut res: Vec<ColoredString> = Vec::new();
+        for entry in &self.entries {
+            res.push(horizontal_line.clone());
+            let etymology_number = entry
+                .etymology_number
+                .map(|number| format!(", etymology {}", number))
+                .unwrap_or_default();
+            res.push(
+                format!(
+                    "{} ({}{})",
+                    paint(&self.word, Role::Headword).bold(),
+                    entry.pos,
+                    etymology_number
+                )
+                .normal(),
+            );
+            res.push(horizontal_line.clone());
+            res.push(format!("{} {}", self.lang_code.italic(), self.word).normal());
+            if entry.ancestors.is_empty() {
+                res.push("No ancestors known".italic());
+            }
+            for (level, node) in entry.ancestors.iter().enumerate() {
+                res.push(format_ancestor(node, level));
+            }
+        }
+        NEWLINE.normal().join(res)
+    }
+}
+
+async fn ancestors_of(
+    dictionaries: &Dictionaries,
+    entry: &DictionaryEntry,
+) -> Result<Vec<AncestorNode>> {
+    let mut ancestors: Vec<AncestorNode> = entry
+        .ancestry()
+        .into_iter()
+        .map(|ancestor| AncestorNode {
+            ancestor,
+            found_in: None,
+            gloss: None,
+        })
+        .collect();
+    let mut lookups = 0;
+    let mut index = 0;
+    while index < ancestors.len() && lookups < MAX_ANCESTOR_LOOKUPS {
+        let ancestor = &ancestors[index].ancestor;
+        let found = match &ancestor.word {
+            Some(word) if dictionaries.covers(&ancestor.language_code) => {
+                // ancestors that aren't found cost a query as well
+                lookups += 1;
+                dictionaries.find(&ancestor.language_code, word).await?
+            }
+            _ => None,
+        };
+        if let Some((language, found)) = found {
+            ancestors[index].found_in = Some(language);
+            ancestors[index].gloss = first_gloss(&found);
+            if index == ancestors.len() - 1 {
+                for ancestor in found.ancestry() {
+                    if !ancestors.iter().any(|node| node.ancestor == ancestor) {
+                        ancestors.push(AncestorNode {
+                            ancestor,
+                            found_in: None,
+                            gloss: None,
+                        });
+                    }
+                }
+            }
+        }
+        index += 1;
+    }
+    Ok(ancestors)
+}
+
+fn format_ancestor(node: &AncestorNode, level: usize) -> ColoredString {
+    let ancestor = &node.ancestor;
+    let mut line = format!(
+        "{}└ {} {} {}",
+        "  ".repeat(level),
+        ancestor.relation.to_string().italic(),
+        ancestor.language,
+        ancestor.word.as_deref().unwrap_or("?").yellow()
+    );
+    if let Some(language) = node.found_in {
+        line.push_str(&format!(" [{}]", language).dimmed().to_string());
+    }
+    if let Some(gloss) = &node.gloss {
+        line.push_str(&format!(" “{}”", gloss));
+    }
+    line.normal()
+}
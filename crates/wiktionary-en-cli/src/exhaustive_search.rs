@@ -3,18 +3,15 @@ use edit_distance::edit_distance;
 use std::fs::File;
 use std::io::{prelude::*, BufReader};
 use std::path::Path;
-use std::sync::atomic::AtomicBool;
-use std::sync::atomic::Ordering;
-use std::sync::Arc;
 use utilities::anyhow_serde;
 use utilities::file_utils::*;
 use wiktionary_en_entities::dictionary_entry::DictionaryEntry;
 use wiktionary_en_entities::result::{DictionaryResult, DidYouMean};
 
-const CHECK_FOR_SOLUTION_FOUND_EVERY: usize = 100;
-
 pub struct ExhaustiveDictionaryResult {
     pub full_matches: Vec<DictionaryEntry>,
+    /// Full matches after the first `max_results`, counted but not kept
+    pub remaining_matches: usize,
     pub did_you_mean: Option<DictionaryEntry>,
     distance: usize,
 }
@@ -32,11 +29,16 @@ fn evaluate_entry(
     term: &str,
     json: DictionaryEntry,
     case_insensitive: bool,
+    max_results: usize,
     min_distance: usize,
 ) -> usize {
     let distance = levenshtein_distance(&json.word, term, case_insensitive);
     if distance == 0 {
-        search_result.full_matches.push(json.clone());
+        if search_result.full_matches.len() < max_results {
+            search_result.full_matches.push(json.clone());
+        } else {
+            search_result.remaining_matches += 1;
+        }
     }
     if distance < min_distance {
         search_result.did_you_mean = Some(json);
@@ -54,15 +56,15 @@ fn parse_line(line: Result<String, std::io::Error>, i: usize) -> Result<Dictiona
         })
 }
 
-fn search_worker(
+fn do_search(
     file_reader: BufReader<File>,
     term: &str,
     max_results: usize,
     case_insensitive: bool,
-    is_solution_found: Arc<AtomicBool>,
 ) -> Result<ExhaustiveDictionaryResult> {
     let mut search_result = ExhaustiveDictionaryResult {
         full_matches: Vec::new(),
+        remaining_matches: 0,
         did_you_mean: None,
         distance: usize::MAX,
     };
@@ -77,59 +79,62 @@ fn search_worker(
                     term,
                     json,
                     case_insensitive,
+                    max_results,
                     min_distance,
                 )
             }
             Err(err) => bail!(err),
         }
-
-        if search_result.full_matches.len() == max_results {
-            is_solution_found.store(true, Ordering::Relaxed);
-            break;
-        }
-        if i % CHECK_FOR_SOLUTION_FOUND_EVERY == 0 && is_solution_found.load(Ordering::Relaxed) {
-            break;
-        }
     }
     Ok(search_result)
 }
 
-fn do_search(
-    file_reader: BufReader<File>,
-    term: &str,
-    max_results: usize,
-    case_insensitive: bool,
-) -> Result<ExhaustiveDictionaryResult> {
-    search_worker(
-        file_reader,
-        term,
-        max_results,
-        case_insensitive,
-        Arc::new(AtomicBool::new(false)),
-    )
-}
-
+/// Scans the whole extract for the page of full matches, pages are
+/// `max_results` long, and counts the matches after the page. Without any
+/// full match the closest word is suggested instead.
 pub fn search(
     input_path: &Path,
     term: &str,
     max_results: usize,
+    page: u64,
+    bookmark: Option<&str>,
     case_insensitive: bool,
 ) -> Result<DictionaryResult> {
+    if bookmark.is_some() {
+        bail!("bookmarks only work with CouchDB, page through the local extract with --page");
+    }
+    let skipped = page.saturating_sub(1) as usize * max_results;
     let buf_reader = get_file_reader(input_path)?;
-    let result = do_search(buf_reader, term, max_results, case_insensitive)?;
-    if let Some(did_you_mean) = result.did_you_mean {
-        return Ok(DictionaryResult {
-            word: term.to_string(),
-            did_you_mean: Some(DidYouMean {
-                searched_for: term.to_string(),
-                suggestion: did_you_mean.word.clone(),
-            }),
-            hits: vec![did_you_mean],
-        });
+    let result = do_search(buf_reader, term, skipped + max_results, case_insensitive)?;
+    if result.full_matches.is_empty() {
+        if let Some(did_you_mean) = result.did_you_mean {
+            return Ok(DictionaryResult {
+                word: term.to_string(),
+                did_you_mean: Some(DidYouMean {
+                    searched_for: term.to_string(),
+                    suggestion: did_you_mean.word.clone(),
+                }),
+                hits: vec![did_you_mean],
+                remaining_hits: 0,
+                form_of: Vec::new(),
+                bookmark: None,
+            });
+        }
+    }
+    if skipped > 0 && result.full_matches.len() <= skipped {
+        bail!(
+            "page {} is out of range, there are only {} results for '{}'",
+            page,
+            result.full_matches.len(),
+            term
+        );
     }
     Ok(DictionaryResult {
         word: term.to_string(),
         did_you_mean: None,
-        hits: result.full_matches,
+        hits: result.full_matches.into_iter().skip(skipped).collect(),
+        remaining_hits: result.remaining_matches,
+        form_of: Vec::new(),
+        bookmark: None,
     })
 }
@@ -0,0 +1,56 @@
+use anyhow::Result;
+use colored::ColoredString;
+use colored::Colorize;
+use std::fmt;
+
+use utilities::colored_string_utils::*;
+use wiktionary_en_db::client::{DbClient, ExtensionCollectionIndex};
+
+pub struct ExtensionIndexes {
+    extension_name: String,
+    indexes: Vec<ExtensionCollectionIndex>,
+}
+
+impl fmt::Display for ExtensionIndexes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.to_pretty_string())
+    }
+}
+
+impl ExtensionIndexes {
+    pub async fn list(client: &DbClient, extension_name: &str) -> Result<Self> {
+        Ok(Self {
+            extension_name: extension_name.to_string(),
+            indexes: client
+                .list_indexes_in_extension_collection(extension_name)
+                .await?,
+        })
+    }
+
+    fn to_pretty_string(&self) -> ColoredString {
+        let mut res: Vec<ColoredString> = Vec::new();
+        res.push(
+            format!(
+                "{} {}",
+                "Indexes in".bold(),
+                self.extension_name.green().bold()
+            )
+            .normal(),
+        );
+        for index in &self.indexes {
+            res.push(format_index(index));
+        }
+        NEWLINE.normal().join(res)
+    }
+}
+
+fn format_index(index: &ExtensionCollectionIndex) -> ColoredString {
+    let mut res: Vec<ColoredString> = vec![format!(" {}", index).normal()];
+    if let Some(ddoc) = &index.ddoc {
+        res.push(format!("   {:<16}: {}", "design document".italic(), ddoc).normal());
+    }
+    if let Some(selector) = &index.partial_filter_selector {
+        res.push(format!("   {:<16}: {}", "partial filter".italic(), selector).normal());
+    }
+    NEWLINE.normal().join(res)
+}
@@ -0,0 +1,50 @@
+use anyhow::Result;
+use colored::Colorize;
+use futures_util::StreamExt;
+use std::io::Write;
+use std::pin::pin;
+
+use wiktionary_en_db::client::{CollectionChange, DbClient};
+
+pub async fn watch(
+    client: &DbClient,
+    extension_name: &str,
+    since: &str,
+    out: &mut impl Write,
+) -> Result<()> {
+    let changes = client
+        .watch_extension_collection(extension_name, since)
+        .await?;
+    let mut changes = pin!(changes);
+    writeln!(
+        out,
+        "{} {}",
+        "Watching".bold(),
+        extension_name.green().bold()
+    )?;
+    while let Some(change) = changes.next().await {
+        writeln!(out, "{}", format_change(&change?))?;
+        out.flush()?;
+    }
+    Ok(())
+}
+
+fn format_change(change: &CollectionChange) -> String {
+    // only the leading counter of a sequence is readable
+    let seq = change.seq.split('-').next().unwrap_or_default();
+    if change.deleted {
+        return format!("[{}] {} {}", seq, change.id.yellow(), "deleted".red());
+    }
+    let document = change
+        .document
+        .as_ref()
+        .map(|document| document.document.to_string())
+        .unwrap_or_default();
+    format!(
+        "[{}] {} {}\n   {}",
+        seq,
+        change.id.yellow(),
+        "updated".green(),
+        document
+    )
+}
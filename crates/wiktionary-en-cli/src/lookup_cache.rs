@@ -0,0 +1,276 @@
+use anyhow::{Context, Result};
+use colored::ColoredString;
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use utilities::colored_string_utils::*;
+use utilities::language::Language;
+use utilities::DICTIONARY_CACHE_DIR;
+use wiktionary_en_entities::result::DictionaryResult;
+
+const CACHE_FILE_NAME: &str = "lookup-cache.json";
+
+macro_rules! format_key_value {
+    ($key:expr, $value:expr) => {
+        format!("{:<19}: {}", $key.green(), $value).normal()
+    };
+}
+
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    language: Language,
+    last_used: u64,
+    result: DictionaryResult,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct CacheContent {
+    /// Incremented on every access, entries with the lowest `last_used` are evicted first
+    tick: u64,
+    /// Import stamps the cached results of each language belong to
+    stamps: HashMap<String, String>,
+    entries: HashMap<String, CacheEntry>,
+}
+
+pub struct CacheKey {
+    language: Language,
+    key: String,
+}
+
+impl CacheKey {
+    pub fn new(
+        language: Language,
+        db_path: &Path,
+        term: &str,
+        max_results: usize,
+        page: u64,
+        bookmark: Option<&str>,
+        case_insensitive: bool,
+    ) -> Self {
+        Self {
+            language,
+            key: format!(
+                "{}|{}|{}|{}|{}|{}|{}",
+                language,
+                db_path.display(),
+                normalize_term(term),
+                max_results,
+                page,
+                bookmark.unwrap_or_default(),
+                case_insensitive
+            ),
+        }
+    }
+}
+
+pub fn normalize_term(term: &str) -> String {
+    term.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+/// Builds the stamp a cached result is valid for from the import stamp of the
+/// database and the modification time of the extract used by the exhaustive search.
+pub fn stamp(import_stamp: Option<String>, extract_path: &Path) -> String {
+    let modified = fs::metadata(extract_path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+        .map(|modified| modified.as_secs().to_string());
+    format!(
+        "{}:{}",
+        import_stamp.unwrap_or("none".to_string()),
+        modified.unwrap_or("none".to_string())
+    )
+}
+
+pub struct LookupCache {
+    path: PathBuf,
+    capacity: usize,
+    content: CacheContent,
+    /// Keys of the entries by `last_used`, so the least recently used one is found without a scan
+    recency: BTreeMap<u64, String>,
+    changed: bool,
+}
+
+impl LookupCache {
+    pub fn open(capacity: usize) -> Self {
+        let path = cache_path();
+        // a broken cache is simply started over
+        let content = fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        let mut cache = Self {
+            path,
+            capacity,
+            content,
+            recency: BTreeMap::new(),
+            changed: false,
+        };
+        cache.index_recency();
+        cache
+    }
+
+    /// Neither returns nor stores results, e.g. when they can't be validated
+    pub fn bypass(&mut self) {
+        self.capacity = 0;
+    }
+
+    fn index_recency(&mut self) {
+        self.recency = self
+            .content
+            .entries
+            .iter()
+            .map(|(key, entry)| (entry.last_used, key.clone()))
+            .collect();
+    }
+
+    /// Drops the results of the language if they were cached for another stamp.
+    pub fn validate(&mut self, language: Language, stamp: &str) {
+        let language_key = language.to_string();
+        if self.content.stamps.get(&language_key).map(String::as_str) == Some(stamp) {
+            return;
+        }
+        self.content
+            .entries
+            .retain(|_, entry| entry.language != language);
+        self.content.stamps.insert(language_key, stamp.to_string());
+        self.index_recency();
+        self.changed = true;
+    }
+
+    /// A hit only refreshes the recency in memory, it is written along with
+    /// the next change so that lookups served from the cache don't rewrite it.
+    pub fn get(&mut self, key: &CacheKey) -> Option<DictionaryResult> {
+        if self.capacity == 0 {
+            return None;
+        }
+        let entry = self.content.entries.get_mut(&key.key)?;
+        self.content.tick += 1;
+        self.recency.remove(&entry.last_used);
+        entry.last_used = self.content.tick;
+        self.recency.insert(entry.last_used, key.key.clone());
+        Some(entry.result.clone())
+    }
+
+    pub fn put(&mut self, key: CacheKey, result: &DictionaryResult) {
+        if self.capacity == 0 {
+            return;
+        }
+        self.content.tick += 1;
+        let replaced = self.content.entries.insert(
+            key.key.clone(),
+            CacheEntry {
+                language: key.language,
+                last_used: self.content.tick,
+                result: result.clone(),
+            },
+        );
+        if let Some(replaced) = replaced {
+            self.recency.remove(&replaced.last_used);
+        }
+        self.recency.insert(self.content.tick, key.key);
+        while self.content.entries.len() > self.capacity {
+            let Some((_, least_recently_used)) = self.recency.pop_first() else {
+                break;
+            };
+            self.content.entries.remove(&least_recently_used);
+        }
+        self.changed = true;
+    }
+
+    /// Writes a temporary file that replaces the cache, so an interrupted
+    /// write or a concurrent lookup never sees half a cache.
+    pub fn save(&self) -> Result<()> {
+        if !self.changed {
+            return Ok(());
+        }
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Couldn't create cache dir: '{}'", parent.display()))?;
+        }
+        let temporary_path = self
+            .path
+            .with_extension(format!("json.{}.tmp", std::process::id()));
+        fs::write(&temporary_path, serde_json::to_string(&self.content)?)
+            .with_context(|| format!("Couldn't write cache: '{}'", temporary_path.display()))?;
+        fs::rename(&temporary_path, &self.path)
+            .with_context(|| format!("Couldn't replace cache: '{}'", self.path.display()))
+    }
+
+    pub fn clear() -> Result<usize> {
+        let cache = Self::open(0);
+        let cleared = cache.content.entries.len();
+        if cache.path.exists() {
+            fs::remove_file(&cache.path)
+                .with_context(|| format!("Couldn't remove cache: '{}'", cache.path.display()))?;
+        }
+        Ok(cleared)
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        let mut entries_per_language: BTreeMap<String, usize> = BTreeMap::new();
+        for entry in self.content.entries.values() {
+            *entries_per_language
+                .entry(entry.language.to_string())
+                .or_default() += 1;
+        }
+        CacheStats {
+            path: self.path.display().to_string(),
+            capacity: self.capacity,
+            entries: self.content.entries.len(),
+            entries_per_language,
+            file_size: fs::metadata(&self.path).map(|metadata| metadata.len()).ok(),
+        }
+    }
+}
+
+pub struct CacheStats {
+    path: String,
+    capacity: usize,
+    entries: usize,
+    entries_per_language: BTreeMap<String, usize>,
+    file_size: Option<u64>,
+}
+
+impl fmt::Display for CacheStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.to_pretty_string())
+    }
+}
+
+impl CacheStats {
+    fn to_pretty_string(&self) -> ColoredString {
+        let mut res: Vec<ColoredString> = Vec::new();
+        res.push(format_key_value!("cache file", self.path));
+        res.push(format_key_value!(
+            "cached lookups",
+            format!(
+                "{} of {}",
+                format_integer(self.entries),
+                format_integer(self.capacity)
+            )
+        ));
+        for (language, entries) in &self.entries_per_language {
+            res.push(format_key_value!(
+                format!("  {}", language),
+                format_integer(*entries)
+            ));
+        }
+        if let Some(file_size) = self.file_size {
+            res.push(format_key_value!(
+                "cache size",
+                format!("{} {}", format_integer(file_size as usize), "bytes")
+            ));
+        }
+        NEWLINE.normal().join(res)
+    }
+}
+
+fn cache_path() -> PathBuf {
+    PathBuf::from(DICTIONARY_CACHE_DIR!()).join(CACHE_FILE_NAME)
+}
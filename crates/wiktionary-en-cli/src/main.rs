@@ -1,15 +1,21 @@
-use anyhow::Result;
+use anyhow::{bail, Result};
 use clap::{Parser, Subcommand};
+use colored::Colorize;
 use std::path::PathBuf;
 
-use utilities::file_utils::get_db_path;
+use utilities::color::ColorChoice;
+use utilities::file_utils::{get_db_path, get_file_reader};
 use utilities::language::Language;
+use utilities::retry::RetryPolicy;
 
+use wiktionary_en_entities::anki::{CardTemplates, DEFAULT_BACK_TEMPLATE, DEFAULT_FRONT_TEMPLATE};
+use wiktionary_en_entities::dictionary_entry::Relation;
+use wiktionary_en_entities::render::RenderFormat;
 use wiktionary_en_entities::result::DictionaryResult;
 #[cfg(feature = "sonic")]
 use wiktionary_en_entities::result::DidYouMean;
 
-use wiktionary_en_db::client::{DbClient, DbClientMutex};
+use wiktionary_en_db::client::{DbClient, DbClientMutex, ImportMode, Pagination};
 
 mod stats;
 use stats::Stats;
@@ -19,6 +25,39 @@ use crate::result_wrapper::WiktionaryResultWrapper;
 
 mod exhaustive_search;
 
+mod extension_indexes;
+use extension_indexes::ExtensionIndexes;
+
+mod extension_watch;
+
+mod data;
+
+mod anki;
+use anki::HistoryFilter;
+
+mod doctor;
+use doctor::Doctor;
+
+mod lookup_cache;
+use lookup_cache::{CacheKey, LookupCache};
+
+mod thesaurus;
+use thesaurus::Thesaurus;
+
+mod dictionaries;
+
+mod etymology;
+use etymology::EtymologyTree;
+
+mod descendants;
+use descendants::DescendantTree;
+
+mod categories;
+use categories::Categories;
+
+mod pronounce;
+use pronounce::Pronunciation;
+
 /// A To English Dictionary
 #[derive(Parser)]
 #[clap(author, version, about, long_about = None)]
@@ -29,22 +68,93 @@ struct Cli {
     /// Set dictionary language
     #[clap(long, short = 'l')]
     language: Option<Language>,
+    /// Color the output: 'auto' only when printing to a terminal, 'always' or 'never'
+    #[clap(long, global = true, default_value = "auto")]
+    color: ColorChoice,
+    /// Print the output directly instead of in a pager
+    #[clap(long, global = true)]
+    no_pager: bool,
     #[command(subcommand)]
-    command: Command,
+    command: CliCommand,
+}
+
+#[derive(Subcommand)]
+enum CliCommand {
+    #[command(flatten)]
+    Configured(Command),
+    /// Check the setup and suggest fixes for what is broken
+    Doctor,
 }
 
+/// Commands that need the configuration
 #[derive(Subcommand)]
 enum Command {
     /// Search in the dictionary
     Search {
-        /// A word to search for
-        search_term: String,
+        /// A word to search for, optional when searching by `--syllables`
+        #[clap(required_unless_present = "syllables")]
+        search_term: Option<String>,
         /// Maximal number of results
-        #[clap(short, long, default_value = "1")]
-        max_results: usize,
+        #[clap(short, long, default_value = "1", value_parser = clap::value_parser!(u64).range(1..))]
+        max_results: u64,
+        /// Page of results to show, pages are `max-results` long
+        #[clap(short, long, default_value = "1", value_parser = clap::value_parser!(u64).range(1..))]
+        page: u64,
+        /// Show the page after the one that printed this bookmark
+        #[clap(long, conflicts_with_all = ["page", "syllables"])]
+        bookmark: Option<String>,
         /// Use case insensitive search
         #[clap(short = 'i', long)]
         case_insensitive: bool,
+        /// Only show the sense with this number, also given as `word#2`
+        #[clap(short, long, value_parser = clap::value_parser!(u64).range(1..))]
+        sense: Option<u64>,
+        /// Only find entries hyphenated into this number of syllables
+        #[clap(long, value_parser = clap::value_parser!(u64).range(1..))]
+        syllables: Option<u64>,
+        /// Render the entries as 'markdown' or 'html' instead of for the terminal
+        #[clap(short, long)]
+        format: Option<RenderFormat>,
+    },
+    /// Show the words related to a word, grouped by relation
+    Thesaurus {
+        /// A word to show the related words of
+        word: String,
+        /// Only show one relation, e.g. 'hypernyms'
+        #[clap(short, long)]
+        relation: Option<Relation>,
+        /// Number of levels to follow each relation, e.g. the hypernyms of the hypernyms
+        #[clap(long, default_value = "1", value_parser = clap::value_parser!(u64).range(1..))]
+        depth: u64,
+    },
+    /// Show the ancestry of a word as a tree
+    Etymology {
+        /// A word to show the ancestry of
+        word: String,
+    },
+    /// Show the words descending from a word, e.g. its borrowings into other languages
+    Descendants {
+        /// A word to show the descendants of
+        word: String,
+    },
+    /// Play a recording of a word from `Config.audio_dir`, or show where to find one
+    Pronounce {
+        /// A word to pronounce
+        word: String,
+        /// Only use recordings of this accent, e.g. 'us' or 'uk'
+        #[clap(short, long)]
+        accent: Option<String>,
+    },
+    /// Find Wiktionary categories by name
+    Categories {
+        /// Part of the category names to find, ignoring case
+        #[clap(short, long)]
+        search: String,
+    },
+    /// List the headwords of a Wiktionary category, e.g. 'en:Dogs'
+    Category {
+        /// The full name of the category
+        name: String,
     },
     /// Show statistics
     Stats,
@@ -55,6 +165,18 @@ enum Command {
         #[command(subcommand)]
         command: SonicCommand,
     },
+    /// Inspect or clear the cache of lookup results
+    Cache {
+        /// Operation
+        #[command(subcommand)]
+        command: CacheCommand,
+    },
+    /// Backup and restore extension data
+    Data {
+        /// Operation
+        #[command(subcommand)]
+        command: DataCommand,
+    },
     /// Call an extension
     Extension {
         /// The name of the extension
@@ -62,6 +184,63 @@ enum Command {
         /// The arguments to the extension
         #[clap(short, long)]
         options: Vec<String>,
+        /// List the indexes of the extension collection instead of calling the extension
+        #[clap(long, conflicts_with_all = ["options", "watch"])]
+        indexes: bool,
+        /// Stream the changes of the extension collection as they happen
+        #[clap(long, conflicts_with = "options")]
+        watch: bool,
+        /// Sequence to start watching after, '0' replays all changes
+        #[clap(long, requires = "watch", default_value = "now")]
+        since: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum CacheCommand {
+    /// Remove all cached lookups
+    Clear,
+    /// Show the number of cached lookups
+    Stats,
+}
+
+#[derive(Subcommand)]
+enum DataCommand {
+    /// Write extension collections to stdout as JSON lines
+    Export {
+        /// Only export the collection of this extension
+        #[clap(short, long)]
+        extension: Option<String>,
+    },
+    /// Restore extension collections from JSON lines
+    Import {
+        /// Only import the collection of this extension
+        #[clap(short, long)]
+        extension: Option<String>,
+        /// Read the backup from a file instead of stdin
+        #[clap(short, long)]
+        file: Option<String>,
+        /// Either 'merge' to keep existing documents or 'overwrite' to recreate the collections
+        #[clap(short, long, default_value = "merge")]
+        mode: ImportMode,
+    },
+    /// Write the words of the history to stdout as an Anki import file
+    Anki {
+        /// Only words looked up at least this many times
+        #[clap(long)]
+        min_count: Option<u64>,
+        /// Only words looked up on this day or later, e.g. 2024-12-31
+        #[clap(long)]
+        since: Option<String>,
+        /// Front of the cards, placeholders are {word}, {pos}, {ipa}, {glosses}, {examples} and {count}
+        #[clap(long, default_value = DEFAULT_FRONT_TEMPLATE)]
+        front: String,
+        /// Back of the cards, with the same placeholders as the front
+        #[clap(long, default_value = DEFAULT_BACK_TEMPLATE)]
+        back: String,
+        /// Deck the cards are imported into
+        #[clap(long, default_value = "Wiktionary")]
+        deck: String,
     },
 }
 
@@ -84,29 +263,72 @@ struct QueryParameters {
     search_term: String,
     language: Language,
     max_results: usize,
+    page: u64,
+    bookmark: Option<String>,
     case_insensitive: bool,
     path: PathBuf,
+    #[cfg_attr(not(feature = "sonic"), allow(dead_code))]
+    retry_policy: RetryPolicy,
+    couchdb_available: bool,
+}
+
+impl QueryParameters {
+    fn pagination(&self) -> Pagination {
+        match &self.bookmark {
+            Some(bookmark) => Pagination::after(bookmark.clone(), self.max_results as u64),
+            None => Pagination::page(self.page, self.max_results as u64),
+        }
+    }
+}
+
+fn warn_unavailable(backend: &str, err: &anyhow::Error) {
+    eprintln!(
+        "{} {} is unavailable, falling back to the local extract: {:#}",
+        "warning:".yellow().bold(),
+        backend,
+        err
+    );
 }
 
 #[cfg(feature = "sonic")]
 async fn search_for_alternative_term(
     client: &DbClient,
-    query_params: &QueryParameters,
+    query_params: &mut QueryParameters,
 ) -> Result<Option<DictionaryResult>> {
-    let did_you_mean = wiktionary_en_identifier_index::did_you_mean(
+    let did_you_mean = match wiktionary_en_identifier_index::did_you_mean(
         &query_params.language,
         &query_params.search_term,
-    )?;
+        &query_params.retry_policy,
+    )
+    .await
+    {
+        Ok(did_you_mean) => did_you_mean,
+        Err(err) => {
+            warn_unavailable("sonic", &err);
+            return Ok(None);
+        }
+    };
     if let Some(did_you_mean) = did_you_mean {
-        let hits = client.find_by_word(&did_you_mean).await?;
-        if !hits.is_empty() {
+        let pagination = query_params.pagination();
+        let page = match client.find_by_word(&did_you_mean, &pagination).await {
+            Ok(page) => page,
+            Err(err) => {
+                warn_unavailable("CouchDB", &err);
+                query_params.couchdb_available = false;
+                return Ok(None);
+            }
+        };
+        if !page.hits.is_empty() {
             let result = DictionaryResult {
                 word: query_params.search_term.clone(),
                 did_you_mean: Some(DidYouMean {
                     searched_for: query_params.search_term.clone(),
                     suggestion: did_you_mean,
                 }),
-                hits,
+                remaining_hits: page.remaining(&pagination),
+                hits: page.hits,
+                form_of: Vec::new(),
+                bookmark: None,
             };
             return Ok(Some(result));
         }
@@ -114,19 +336,92 @@ async fn search_for_alternative_term(
     Ok(None)
 }
 
+/// Resolves an inflected form such as "dictionaries" to the entries of its lemma.
+async fn search_for_lemma(
+    client: &DbClient,
+    query_params: &mut QueryParameters,
+) -> Result<Option<DictionaryResult>> {
+    let pagination = query_params.pagination();
+    let page = match client
+        .find_by_form(&query_params.search_term, &pagination)
+        .await
+    {
+        Ok(page) => page,
+        Err(err) => {
+            warn_unavailable("CouchDB", &err);
+            query_params.couchdb_available = false;
+            return Ok(None);
+        }
+    };
+    if page.hits.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(DictionaryResult {
+        word: query_params.search_term.clone(),
+        did_you_mean: None,
+        remaining_hits: page.remaining(&pagination),
+        hits: page.hits,
+        form_of: page.form_of,
+        bookmark: None,
+    }))
+}
+
+/// Looks the term up in CouchDB, then as an inflected form of a lemma, asks
+/// sonic for an alternative and finally scans the local extract. An
+/// unavailable backend is skipped.
 async fn search_for_term(
     client: &DbClient,
     term: &str,
-    query_params: &QueryParameters,
+    query_params: &mut QueryParameters,
 ) -> Result<DictionaryResult> {
-    let hits = client.find_by_word(term).await?;
-    match hits.as_slice() {
+    let pagination = query_params.pagination();
+    let page = if query_params.couchdb_available {
+        match client.find_by_word(term, &pagination).await {
+            Ok(page) => Some(page),
+            Err(err) => {
+                warn_unavailable("CouchDB", &err);
+                query_params.couchdb_available = false;
+                None
+            }
+        }
+    } else {
+        None
+    };
+    let Some(page) = page else {
+        return exhaustive_search::search(
+            &query_params.path,
+            term,
+            query_params.max_results,
+            query_params.page,
+            query_params.bookmark.as_deref(),
+            query_params.case_insensitive,
+        );
+    };
+    match page.hits.as_slice() {
         [_, ..] => Ok(DictionaryResult {
             word: term.to_string(),
             did_you_mean: None,
-            hits,
+            remaining_hits: page.remaining(&pagination),
+            bookmark: page.next_bookmark(&pagination),
+            hits: page.hits,
+            form_of: Vec::new(),
         }),
+        [] if query_params.bookmark.is_some() => {
+            bail!(
+                "there are no more results for '{}' after the bookmark",
+                term
+            )
+        }
+        [] if page.total > 0 => bail!(
+            "page {} is out of range, there are only {} results for '{}'",
+            query_params.page,
+            page.total,
+            term
+        ),
         [] => {
+            if let Some(result) = search_for_lemma(client, query_params).await? {
+                return Ok(result);
+            }
             #[cfg(feature = "sonic")]
             if let Some(result) = search_for_alternative_term(client, query_params).await? {
                 return Ok(result);
@@ -135,76 +430,411 @@ async fn search_for_term(
                 &query_params.path,
                 term,
                 query_params.max_results,
+                query_params.page,
+                query_params.bookmark.as_deref(),
                 query_params.case_insensitive,
             )
         }
     }
 }
 
-async fn query_dictionary(
+/// Finds the entries hyphenated into the number of syllables, of any word
+/// unless a search term is given.
+async fn search_by_syllable_count(
     client: &DbClient,
+    count: u64,
     query_params: QueryParameters,
     extension_handler: wiktionary_en_lua::extension::ExtensionHandler,
 ) -> Result<WiktionaryResultWrapper> {
-    let result = search_for_term(client, &query_params.search_term, &query_params).await?;
+    let term = Some(query_params.search_term.as_str()).filter(|term| !term.is_empty());
+    let pagination = query_params.pagination();
+    let page = client
+        .find_by_syllable_count(count, term, &pagination)
+        .await?;
+    if page.hits.is_empty() {
+        match term {
+            _ if page.total > 0 => bail!(
+                "page {} is out of range, there are only {} results",
+                query_params.page,
+                page.total
+            ),
+            Some(term) => bail!("'{}' has no entry with {} syllables", term, count),
+            None => bail!("no entries with {} syllables found", count),
+        }
+    }
+    let result = DictionaryResult {
+        word: term.map_or(format!("{} syllables", count), String::from),
+        did_you_mean: None,
+        remaining_hits: page.remaining(&pagination),
+        bookmark: None,
+        hits: page.hits,
+        form_of: Vec::new(),
+    };
+    Ok(WiktionaryResultWrapper {
+        result: result_wrapper::WiktionaryResult::DictionaryResult(result),
+        extension_handler,
+        couchdb_available: query_params.couchdb_available,
+    })
+}
+
+/// Splits an addressed sense off a search term, e.g. "dictionary#2"
+fn split_sense(term: &str) -> (&str, Option<usize>) {
+    match term.rsplit_once('#') {
+        Some((word, number)) if !word.is_empty() => match number.parse::<usize>() {
+            Ok(number) if number > 0 => (word, Some(number)),
+            _ => (term, None),
+        },
+        _ => (term, None),
+    }
+}
+
+async fn query_dictionary(
+    client: &DbClient,
+    mut query_params: QueryParameters,
+    extension_handler: wiktionary_en_lua::extension::ExtensionHandler,
+    cache: &mut LookupCache,
+) -> Result<WiktionaryResultWrapper> {
+    let search_term = query_params.search_term.clone();
+    let key = CacheKey::new(
+        query_params.language,
+        &query_params.path,
+        &search_term,
+        query_params.max_results,
+        query_params.page,
+        query_params.bookmark.as_deref(),
+        query_params.case_insensitive,
+    );
+    let result = match cache.get(&key) {
+        Some(result) => result,
+        None => {
+            let result = search_for_term(client, &search_term, &mut query_params).await?;
+            // results of a degraded search would hide the complete ones later on
+            if query_params.couchdb_available {
+                cache.put(key, &result);
+            }
+            result
+        }
+    };
+    cache.save()?;
     Ok(WiktionaryResultWrapper {
         result: result_wrapper::WiktionaryResult::DictionaryResult(result),
         extension_handler,
+        couchdb_available: query_params.couchdb_available,
     })
 }
 
-#[tokio::main]
-async fn main() -> Result<()> {
+fn main() -> Result<()> {
+    let runtime = tokio::runtime::Runtime::new()?;
+    let result = runtime.block_on(run());
+    // sonic calls that timed out still block a thread, waiting for them would hang the exit
+    runtime.shutdown_background();
+    result
+}
+
+async fn run() -> Result<()> {
     let args = Cli::parse();
+    args.color.apply();
+    if args.no_pager {
+        utilities::pager::disable_pager();
+    }
+    let command = match args.command {
+        CliCommand::Configured(command) => command,
+        CliCommand::Doctor => {
+            // the doctor must also run when the configuration is broken
+            let doctor = Doctor::examine(args.language, args.db_path).await;
+            utilities::pager::print_in_pager(&doctor.to_string())?;
+            if doctor.failures() > 0 {
+                bail!("{} checks failed", doctor.failures());
+            }
+            return Ok(());
+        }
+    };
     let config_handler = wiktionary_en_lua::config::ConfigHandler::init()?;
     let language_to_use = config_handler
         .config
         .or_use_config_or_default(args.language);
+    if let Some(width) = config_handler.config.width {
+        utilities::colored_string_utils::set_line_width(width);
+    }
+    utilities::theme::set_theme(config_handler.config.theme.clone());
 
-    let result = match args.command {
+    let result = match command {
         Command::Search {
             search_term,
             max_results,
+            page,
+            bookmark,
             case_insensitive,
+            sense,
+            syllables,
+            format,
         } => {
-            let db_client = DbClient::init(language_to_use).await?;
+            let (search_term, addressed_sense) =
+                split_sense(search_term.as_deref().unwrap_or_default());
+            let sense = match (sense, addressed_sense) {
+                (Some(sense), Some(addressed_sense)) if sense as usize != addressed_sense => {
+                    bail!(
+                        "sense {} conflicts with the sense {} in the search term",
+                        sense,
+                        addressed_sense
+                    )
+                }
+                (sense, addressed_sense) => sense.map(|sense| sense as usize).or(addressed_sense),
+            };
+            let retry_policy = config_handler.config.retry_policy;
+            let (db_client, couchdb_available) =
+                match DbClient::init_with_retry_policy(language_to_use, retry_policy).await {
+                    Ok(db_client) => (db_client, true),
+                    Err(err) => {
+                        warn_unavailable("CouchDB", &err);
+                        (
+                            DbClient::connect_with_retry_policy(language_to_use, retry_policy)?,
+                            false,
+                        )
+                    }
+                };
+            let path = get_db_path(args.db_path, &language_to_use);
+            let mut cache = LookupCache::open(config_handler.config.cache_size);
+            if couchdb_available {
+                match db_client.import_stamp().await {
+                    Ok(import_stamp) => {
+                        cache.validate(language_to_use, &lookup_cache::stamp(import_stamp, &path))
+                    }
+                    Err(err) => {
+                        eprintln!(
+                            "{} the lookup cache can't be validated and isn't used: {:#}",
+                            "warning:".yellow().bold(),
+                            err
+                        );
+                        cache.bypass();
+                    }
+                }
+            } else {
+                // only the extract is searched, so its modification time alone tells
+                // whether cached results are current
+                cache.validate(language_to_use, &lookup_cache::stamp(None, &path));
+            }
             let db_client_mutex = DbClientMutex::from(db_client.clone());
             let extension_handler =
                 wiktionary_en_lua::extension::ExtensionHandler::init(db_client_mutex).await?;
-            let mut result = query_dictionary(
-                &db_client,
-                QueryParameters {
-                    search_term,
-                    language: language_to_use,
-                    max_results,
-                    case_insensitive,
-                    path: get_db_path(args.db_path, &language_to_use),
-                },
-                extension_handler,
-            )
-            .await?;
+            let query_params = QueryParameters {
+                search_term: lookup_cache::normalize_term(search_term),
+                language: language_to_use,
+                max_results: max_results as usize,
+                page,
+                bookmark,
+                case_insensitive,
+                path,
+                retry_policy,
+                couchdb_available,
+            };
+            let mut result = match syllables {
+                Some(syllables) if couchdb_available => {
+                    search_by_syllable_count(&db_client, syllables, query_params, extension_handler)
+                        .await?
+                }
+                Some(_) if query_params.search_term.is_empty() => {
+                    bail!("finding words by their syllables without a search term needs CouchDB")
+                }
+                _ => {
+                    let mut result =
+                        query_dictionary(&db_client, query_params, extension_handler, &mut cache)
+                            .await?;
+                    // the local extract has no syllable index, its hits are filtered instead
+                    if let Some(syllables) = syllables {
+                        result.select_syllable_count(syllables as usize)?;
+                    }
+                    result
+                }
+            };
+            if let Some(sense) = sense {
+                result.select_sense(sense)?;
+            }
 
             result.intercept().await?;
-            result.fmt().await?
+            match format {
+                Some(format) => result.render(format),
+                None => result.fmt().await?,
+            }
         }
         #[cfg(feature = "sonic")]
         Command::Sonic { command } => match command {
             SonicCommand::Auto { word } => {
-                let result = wiktionary_en_identifier_index::suggest(&language_to_use, &word)?;
+                let result = wiktionary_en_identifier_index::suggest(
+                    &language_to_use,
+                    &word,
+                    &config_handler.config.retry_policy,
+                )
+                .await?;
                 result.join("\n")
             }
             SonicCommand::Query { word } => {
-                let result = wiktionary_en_identifier_index::query(&language_to_use, &word)?;
+                let result = wiktionary_en_identifier_index::query(
+                    &language_to_use,
+                    &word,
+                    &config_handler.config.retry_policy,
+                )
+                .await?;
                 result.join("\n")
             }
         },
+        Command::Thesaurus {
+            word,
+            relation,
+            depth,
+        } => {
+            let db_client = DbClient::init_with_retry_policy(
+                language_to_use,
+                config_handler.config.retry_policy,
+            )
+            .await?;
+            Thesaurus::lookup(&db_client, &word, relation, depth as usize)
+                .await?
+                .to_string()
+        }
+        Command::Etymology { word } => {
+            let retry_policy = config_handler.config.retry_policy;
+            let db_client = DbClient::init_with_retry_policy(language_to_use, retry_policy).await?;
+            EtymologyTree::lookup(&db_client, &word, retry_policy)
+                .await?
+                .to_string()
+        }
+        Command::Descendants { word } => {
+            let retry_policy = config_handler.config.retry_policy;
+            let db_client = DbClient::init_with_retry_policy(language_to_use, retry_policy).await?;
+            DescendantTree::lookup(&db_client, &word, retry_policy)
+                .await?
+                .to_string()
+        }
+        Command::Pronounce { word, accent } => {
+            let db_client = DbClient::init_with_retry_policy(
+                language_to_use,
+                config_handler.config.retry_policy,
+            )
+            .await?;
+            Pronunciation::lookup(
+                &db_client,
+                &word,
+                accent.as_deref(),
+                config_handler.config.audio_dir.as_deref(),
+            )
+            .await?
+            .play(config_handler.config.audio_player.as_deref())?
+        }
+        Command::Categories { search } => {
+            let db_client = DbClient::init_with_retry_policy(
+                language_to_use,
+                config_handler.config.retry_policy,
+            )
+            .await?;
+            Categories::search(&db_client, &search).await?.to_string()
+        }
+        Command::Category { name } => {
+            let db_client = DbClient::init_with_retry_policy(
+                language_to_use,
+                config_handler.config.retry_policy,
+            )
+            .await?;
+            Categories::members(&db_client, &name).await?.to_string()
+        }
         Command::Stats => {
             let input_path = get_db_path(args.db_path, &language_to_use);
             let stats = Stats::calculate_stats(&input_path, &language_to_use).await?;
             stats.to_string()
         }
-        Command::Extension { name, options } => {
-            let db_client = DbClient::init(language_to_use).await?;
+        Command::Cache { command } => match command {
+            CacheCommand::Clear => format!("cleared {} cached lookups", LookupCache::clear()?),
+            CacheCommand::Stats => LookupCache::open(config_handler.config.cache_size)
+                .stats()
+                .to_string(),
+        },
+        Command::Data { command } => {
+            let db_client = DbClient::init_with_retry_policy(
+                language_to_use,
+                config_handler.config.retry_policy,
+            )
+            .await?;
+            match command {
+                DataCommand::Export { extension } => {
+                    return data::export(&db_client, extension, &mut std::io::stdout().lock())
+                        .await;
+                }
+                DataCommand::Import {
+                    extension,
+                    file,
+                    mode,
+                } => {
+                    let report = match file {
+                        Some(file) => {
+                            let reader = get_file_reader(&PathBuf::from(file))?;
+                            data::import(&db_client, reader, extension, mode).await?
+                        }
+                        None => {
+                            data::import(&db_client, std::io::stdin().lock(), extension, mode)
+                                .await?
+                        }
+                    };
+                    report.to_string()
+                }
+                DataCommand::Anki {
+                    min_count,
+                    since,
+                    front,
+                    back,
+                    deck,
+                } => {
+                    let export = anki::export(
+                        &db_client,
+                        language_to_use,
+                        &HistoryFilter { min_count, since },
+                        &CardTemplates::init(front, back)?,
+                        &deck,
+                        &mut std::io::stdout().lock(),
+                    )
+                    .await?;
+                    eprintln!("exported {} cards", export.cards);
+                    if !export.skipped.is_empty() {
+                        eprintln!(
+                            "{} no entries found for: {}",
+                            "warning:".yellow(),
+                            export.skipped.join(", ")
+                        );
+                    }
+                    return Ok(());
+                }
+            }
+        }
+        Command::Extension {
+            name,
+            indexes: true,
+            ..
+        } => {
+            let db_client = DbClient::init_with_retry_policy(
+                language_to_use,
+                config_handler.config.retry_policy,
+            )
+            .await?;
+            ExtensionIndexes::list(&db_client, &name).await?.to_string()
+        }
+        Command::Extension {
+            name,
+            watch: true,
+            since,
+            ..
+        } => {
+            let db_client = DbClient::init_with_retry_policy(
+                language_to_use,
+                config_handler.config.retry_policy,
+            )
+            .await?;
+            return extension_watch::watch(&db_client, &name, &since, &mut std::io::stdout()).await;
+        }
+        Command::Extension { name, options, .. } => {
+            let db_client = DbClient::init_with_retry_policy(
+                language_to_use,
+                config_handler.config.retry_policy,
+            )
+            .await?;
             let db_client_mutex = DbClientMutex::from(db_client.clone());
             let extension_handler =
                 wiktionary_en_lua::extension::ExtensionHandler::init(db_client_mutex).await?;
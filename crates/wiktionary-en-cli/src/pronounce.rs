@@ -0,0 +1,160 @@
+use anyhow::{bail, Result};
+use colored::Colorize;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use wiktionary_en_db::client::DbClient;
+use wiktionary_en_entities::dictionary_entry::Sound;
+
+/// Players tried in order when `Config.audio_player` isn't set
+const DEFAULT_AUDIO_PLAYERS: [&str; 4] = [
+    "mpv --no-video --really-quiet",
+    "ffplay -nodisp -autoexit -loglevel quiet",
+    "paplay",
+    "afplay",
+];
+
+struct Recording {
+    sound: Sound,
+    local_file: Option<PathBuf>,
+}
+
+pub struct Pronunciation {
+    word: String,
+    recording: Recording,
+}
+
+impl Pronunciation {
+    /// Picks a recording of the word, one in the audio directory if there is
+    /// any. With an accent only recordings of that accent are considered.
+    pub async fn lookup(
+        client: &DbClient,
+        word: &str,
+        accent: Option<&str>,
+        audio_dir: Option<&Path>,
+    ) -> Result<Self> {
+        let hits = client.find_all_by_word(word).await?;
+        if hits.is_empty() {
+            bail!("no entries found for '{}'", word);
+        }
+        let mut recordings: Vec<Recording> = Vec::new();
+        for sound in hits.into_iter().flat_map(|hit| hit.sounds) {
+            let Some(audio) = &sound.audio else {
+                continue;
+            };
+            if recordings
+                .iter()
+                .any(|recording| recording.sound.audio.as_ref() == Some(audio))
+            {
+                continue;
+            }
+            recordings.push(Recording {
+                local_file: audio_dir.and_then(|audio_dir| find_local_file(audio_dir, &sound)),
+                sound,
+            });
+        }
+        if recordings.is_empty() {
+            bail!("no recordings known for '{}'", word);
+        }
+        if let Some(accent) = accent {
+            let mut accents: Vec<String> = recordings
+                .iter()
+                .flat_map(|recording| recording.sound.accents())
+                .collect();
+            accents.sort();
+            accents.dedup();
+            recordings.retain(|recording| recording.sound.has_accent(accent));
+            if recordings.is_empty() {
+                bail!(
+                    "no recording of '{}' with the accent '{}', try one of: {}",
+                    word,
+                    accent,
+                    accents.join(", ")
+                );
+            }
+        }
+        let index = recordings
+            .iter()
+            .position(|recording| recording.local_file.is_some())
+            .unwrap_or_default();
+        Ok(Self {
+            word: word.to_string(),
+            recording: recordings.swap_remove(index),
+        })
+    }
+
+    /// Plays the local recording and tells what was played, without one the
+    /// URLs of the recording are returned instead.
+    pub fn play(&self, audio_player: Option<&str>) -> Result<String> {
+        let sound = &self.recording.sound;
+        let Some(local_file) = &self.recording.local_file else {
+            let urls: Vec<&str> = [&sound.ogg_url, &sound.mp3_url]
+                .into_iter()
+                .flatten()
+                .map(String::as_str)
+                .collect();
+            if urls.is_empty() {
+                bail!(
+                    "'{}' isn't in the audio directory and has no URL",
+                    sound.audio.as_deref().unwrap_or(&self.word)
+                );
+            }
+            return Ok(urls.join("\n"));
+        };
+        let players: Vec<&str> = match audio_player {
+            Some(audio_player) => vec![audio_player],
+            None => DEFAULT_AUDIO_PLAYERS.to_vec(),
+        };
+        // a default player that fails, e.g. without an audio device, makes way for the next one
+        let mut failures: Vec<String> = Vec::new();
+        for player in players {
+            let mut args = player.split_whitespace();
+            let Some(program) = args.next() else {
+                continue;
+            };
+            let failure = match Command::new(program).args(args).arg(local_file).status() {
+                Ok(status) if status.success() => {
+                    return Ok(format!("{} {}", "played".green(), local_file.display()))
+                }
+                Ok(status) => format!("'{}' failed with {}", player, status),
+                Err(err) if err.kind() == ErrorKind::NotFound => continue,
+                Err(err) => format!("couldn't start '{}': {}", player, err),
+            };
+            if audio_player.is_some() {
+                bail!(failure);
+            }
+            failures.push(failure);
+        }
+        if !failures.is_empty() {
+            bail!(
+                "no audio player could play {}: {}",
+                local_file.display(),
+                failures.join(", ")
+            );
+        }
+        Ok(format!(
+            "{}\n{}",
+            local_file.display(),
+            "no audio player found, set one with Config.audio_player".yellow()
+        ))
+    }
+}
+
+/// Looks for the recording under its file name, also with underscores for
+/// spaces, and in the hashed folders Commons stores files in, e.g. `1/1f/`.
+fn find_local_file(audio_dir: &Path, sound: &Sound) -> Option<PathBuf> {
+    let audio = sound.audio.as_ref()?;
+    let mut candidates = vec![
+        audio_dir.join(audio),
+        audio_dir.join(audio.replace(' ', "_")),
+    ];
+    if let Some((_, commons_path)) = sound
+        .ogg_url
+        .as_ref()
+        .and_then(|url| url.split_once("/commons/"))
+    {
+        candidates.push(audio_dir.join(commons_path));
+    }
+    candidates.into_iter().find(|candidate| candidate.is_file())
+}
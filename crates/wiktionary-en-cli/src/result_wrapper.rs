@@ -1,5 +1,6 @@
 use anyhow::{bail, Result};
 
+use wiktionary_en_entities::render::RenderFormat;
 use wiktionary_en_entities::result::DictionaryResult;
 use wiktionary_en_lua::extension::ExtensionHandler;
 
@@ -10,10 +11,15 @@ pub enum WiktionaryResult {
 pub struct WiktionaryResultWrapper {
     pub result: WiktionaryResult,
     pub extension_handler: ExtensionHandler,
+    /// Extensions such as the history need CouchDB to intercept a result
+    pub couchdb_available: bool,
 }
 
 impl WiktionaryResultWrapper {
     pub async fn intercept(&mut self) -> Result<()> {
+        if !self.couchdb_available {
+            return Ok(());
+        }
         match &mut self.result {
             WiktionaryResult::DictionaryResult(result) => {
                 self.extension_handler
@@ -23,6 +29,39 @@ impl WiktionaryResultWrapper {
         }
     }
 
+    pub fn select_sense(&mut self, number: usize) -> Result<()> {
+        match &mut self.result {
+            WiktionaryResult::DictionaryResult(result) => {
+                let with_sense = result.clone().with_sense(number);
+                if with_sense.hits.is_empty() && !result.hits.is_empty() {
+                    bail!("'{}' has no sense {}", result.word, number);
+                }
+                *result = with_sense;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn select_syllable_count(&mut self, count: usize) -> Result<()> {
+        match &mut self.result {
+            WiktionaryResult::DictionaryResult(result) => {
+                let with_syllable_count = result.clone().with_syllable_count(count);
+                if with_syllable_count.hits.is_empty() && !result.hits.is_empty() {
+                    bail!("'{}' has no entry with {} syllables", result.word, count);
+                }
+                *result = with_syllable_count;
+            }
+        }
+        Ok(())
+    }
+
+    /// Renders the result without lua formatters, they only target the terminal
+    pub fn render(&self, format: RenderFormat) -> String {
+        match &self.result {
+            WiktionaryResult::DictionaryResult(result) => result.render(format),
+        }
+    }
+
     pub async fn fmt(&self) -> Result<String> {
         match &self.result {
             WiktionaryResult::DictionaryResult(result) => {
@@ -54,6 +93,10 @@ async fn fmt_dictionary_result(
         }
     }
 
+    for form_of in &dictionary_result.form_of {
+        formatted.push(form_of.to_string());
+    }
+
     match extension_handler
         .format_dictionary_entries(&dictionary_result.hits)
         .await
@@ -62,16 +105,18 @@ async fn fmt_dictionary_result(
             for hit in &formated_hits {
                 formatted.push(hit.to_string());
             }
-            Ok(formatted.join("\n"))
         }
         Ok(None) => {
             for entry in &dictionary_result.hits {
                 formatted.push(entry.to_string());
             }
-            Ok(formatted.join("\n"))
         }
         Err(err) => {
             bail!(err)
         }
     }
+    if let Some(more_results) = dictionary_result.more_results_banner() {
+        formatted.push(more_results);
+    }
+    Ok(formatted.join("\n"))
 }
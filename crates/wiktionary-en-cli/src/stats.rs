@@ -8,6 +8,7 @@ use std::path::Path;
 use utilities::colored_string_utils::*;
 use utilities::file_utils::*;
 use utilities::language::Language;
+use utilities::theme::{paint, Role};
 use wiktionary_en_db::client::DbClient;
 
 macro_rules! format_key_value {
@@ -18,7 +19,7 @@ macro_rules! format_key_value {
 
 macro_rules! format_float {
     ($value:expr) => {
-        format!("{:.2}", $value).yellow()
+        paint(&format!("{:.2}", $value), Role::Number)
     };
 }
 
@@ -0,0 +1,184 @@
+use anyhow::{bail, Result};
+use colored::ColoredString;
+use colored::Colorize;
+use std::collections::HashSet;
+use std::fmt;
+
+use utilities::colored_string_utils::*;
+use utilities::theme::{paint, Role};
+use wiktionary_en_db::client::DbClient;
+use wiktionary_en_entities::dictionary_entry::{DictionaryEntry, RelatedWord, Relation};
+
+struct RelatedNode {
+    word: String,
+    tags: Vec<String>,
+    sense: Option<String>,
+    /// Words of the same relation, e.g. the hypernyms of a hypernym
+    children: Vec<RelatedNode>,
+}
+
+struct RelationGroup {
+    relation: Relation,
+    nodes: Vec<RelatedNode>,
+}
+
+pub struct Thesaurus {
+    word: String,
+    groups: Vec<RelationGroup>,
+}
+
+impl fmt::Display for Thesaurus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.to_pretty_string())
+    }
+}
+
+impl Thesaurus {
+    /// Groups the related words of all entries of the word by relation. With a
+    /// depth above one the related words are looked up in turn and the words
+    /// of the same relation are added below them.
+    pub async fn lookup(
+        client: &DbClient,
+        word: &str,
+        relation: Option<Relation>,
+        depth: usize,
+    ) -> Result<Self> {
+        let entries = client.find_all_by_word(word).await?;
+        if entries.is_empty() {
+            bail!("no entries found for '{}'", word);
+        }
+        let mut groups = Vec::new();
+        for relation in Relation::iterator().filter(|r| relation.is_none_or(|wanted| wanted == *r))
+        {
+            let nodes = related_nodes(client, word, &entries, relation, depth).await?;
+            if !nodes.is_empty() {
+                groups.push(RelationGroup { relation, nodes });
+            }
+        }
+        Ok(Self {
+            word: word.to_string(),
+            groups,
+        })
+    }
+
+    fn to_pretty_string(&self) -> ColoredString {
+        let horizontal_line = horizontal_line();
+        let mut res: Vec<ColoredString> = vec![
+            horizontal_line.clone(),
+            paint(&self.word, Role::Headword).bold(),
+            horizontal_line,
+        ];
+        if self.groups.is_empty() {
+            res.push("No related words found".italic());
+        }
+        for group in &self.groups {
+            res.push(paint(group.relation.title(), Role::SectionTitle).bold());
+            for (i, node) in group.nodes.iter().enumerate() {
+                res.push(format_node(
+                    node,
+                    &format!(" {}. ", i.to_string().italic()),
+                    0,
+                ));
+            }
+        }
+        NEWLINE.normal().join(res)
+    }
+}
+
+/// Walks the relation a level at a time, so that every level takes one query
+/// for all of its words rather than one per word.
+async fn related_nodes(
+    client: &DbClient,
+    word: &str,
+    entries: &[DictionaryEntry],
+    relation: Relation,
+    depth: usize,
+) -> Result<Vec<RelatedNode>> {
+    // visited guards against cycles such as synonyms listing each other
+    let mut visited = HashSet::from([word.to_string()]);
+    // the related words of every level with the index of their parent in the level above
+    let mut levels: Vec<Vec<(usize, RelatedWord)>> = vec![unvisited_related_words(
+        entries.iter().map(|entry| (0, entry)),
+        relation,
+        &mut visited,
+    )];
+    while levels.len() < depth {
+        let Some(level) = levels.last().filter(|level| !level.is_empty()) else {
+            break;
+        };
+        let words: Vec<String> = level
+            .iter()
+            .map(|(_, related_word)| related_word.word.clone())
+            .collect();
+        let related_entries = client.find_by_words(&words).await?;
+        let parents = level
+            .iter()
+            .enumerate()
+            .flat_map(|(parent, (_, related_word))| {
+                related_entries
+                    .iter()
+                    .filter(move |entry| entry.word == related_word.word)
+                    .map(move |entry| (parent, entry))
+            });
+        let next_level = unvisited_related_words(parents, relation, &mut visited);
+        levels.push(next_level);
+    }
+
+    // the tree is built from the deepest level up, children are grouped by the index of their parent
+    let mut children_by_parent: Vec<Vec<RelatedNode>> = Vec::new();
+    for level in levels.into_iter().rev() {
+        let mut children = children_by_parent.into_iter();
+        let mut nodes_by_parent: Vec<Vec<RelatedNode>> = Vec::new();
+        for (parent, related_word) in level {
+            if nodes_by_parent.len() <= parent {
+                nodes_by_parent.resize_with(parent + 1, Vec::new);
+            }
+            nodes_by_parent[parent].push(RelatedNode {
+                word: related_word.word,
+                tags: related_word.tags,
+                sense: related_word.sense,
+                children: children.next().unwrap_or_default(),
+            });
+        }
+        children_by_parent = nodes_by_parent;
+    }
+    Ok(children_by_parent.into_iter().next().unwrap_or_default())
+}
+
+fn unvisited_related_words<'a>(
+    entries: impl Iterator<Item = (usize, &'a DictionaryEntry)>,
+    relation: Relation,
+    visited: &mut HashSet<String>,
+) -> Vec<(usize, RelatedWord)> {
+    let mut related_words = Vec::new();
+    for (parent, entry) in entries {
+        for related_word in entry.related_words(relation) {
+            if visited.insert(related_word.word.clone()) {
+                related_words.push((parent, related_word.clone()));
+            }
+        }
+    }
+    related_words
+}
+
+fn format_node(node: &RelatedNode, prefix: &str, level: usize) -> ColoredString {
+    let mut clarifications = Vec::new();
+    if !node.tags.is_empty() {
+        clarifications.push(format!("({})", node.tags.join(", ")));
+    }
+    if let Some(sense) = &node.sense {
+        clarifications.push(format!("[{}]", sense));
+    }
+    let mut res: Vec<ColoredString> = vec![format!(
+        "{}{}{} {}",
+        "  ".repeat(level),
+        prefix,
+        node.word,
+        clarifications.join(" ").italic()
+    )
+    .normal()];
+    for child in &node.children {
+        res.push(format_node(child, "   └ ", level + 1));
+    }
+    NEWLINE.normal().join(res)
+}
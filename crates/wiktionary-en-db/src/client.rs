@@ -1,25 +1,36 @@
 use anyhow::{bail, Context, Result};
 use couch_rs::database::Database;
 use couch_rs::document::DocumentCollection;
+use couch_rs::document::TypedCouchDocument;
 use couch_rs::error::CouchError;
 use couch_rs::error::ErrorDetails;
-use couch_rs::http::StatusCode;
+use couch_rs::http::{Method, StatusCode};
 use couch_rs::types::find::FindQuery;
-use couch_rs::types::find::SortSpec;
+use couch_rs::types::find::{SortDirection, SortSpec};
 use couch_rs::types::index::IndexFields;
+use couch_rs::types::query::QueryParams;
 use couch_rs::types::view::ViewCollection;
 use couch_rs::types::view::{CouchFunc, CouchViews};
 use couch_rs::Client;
-use serde_json::{json, Value};
+use futures_util::{stream, Stream, StreamExt, TryStreamExt};
+use serde::de::DeserializeOwned;
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
 use std::env;
+use std::fmt;
 use std::fs::File;
+use std::future::Future;
 use std::io::{BufRead, BufReader};
+use std::str::FromStr;
 use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
 use std::vec;
 use std::vec::Vec;
 use tokio::sync::Mutex;
 use utilities::language::Language;
-use wiktionary_en_entities::dictionary_entry::DictionaryEntry;
+use utilities::retry::RetryPolicy;
+use wiktionary_en_entities::dictionary_entry::{DictionaryEntry, NON_FORM_TAGS};
+use wiktionary_en_entities::result::FormOf;
 
 macro_rules! extension_database {
     ($language:expr, $extension_name:expr) => {
@@ -27,14 +38,26 @@ macro_rules! extension_database {
     };
 }
 
+const WORD_INDEX_NAME: &str = "word-index";
+const IMPORT_STAMP_DOC_NAME: &str = "import-stamp";
 const ANALYTICS_DESIGN_DOC_NAME: &str = "analytics";
 const WORD_COUNT_VIEW_NAME: &str = "word_count";
+const FORMS_DESIGN_DOC_NAME: &str = "forms";
+const BY_FORM_VIEW_NAME: &str = "by_form";
+const CATEGORIES_DESIGN_DOC_NAME: &str = "categories";
+const BY_CATEGORY_VIEW_NAME: &str = "by_category";
+const WORDS_DESIGN_DOC_NAME: &str = "words";
+const BY_WORD_VIEW_NAME: &str = "by_word";
+const SYLLABLES_DESIGN_DOC_NAME: &str = "syllables";
+const BY_SYLLABLE_COUNT_VIEW_NAME: &str = "by_syllable_count";
+const BULK_BATCH_SIZE: u64 = 500;
 
 #[derive(Clone)]
 pub struct DbClient {
     client: Client,
     database: Database,
     language: Language,
+    retry_policy: RetryPolicy,
 }
 
 #[derive(Clone)]
@@ -84,45 +107,807 @@ impl ExtensionCollectionViewDefinition {
     }
 }
 
+struct ExtensionCollectionFindOptions {
+    options: Map<String, Value>,
+}
+
+impl ExtensionCollectionFindOptions {
+    const KEYS: [&str; 6] = ["sort", "limit", "skip", "fields", "use_index", "bookmark"];
+
+    pub fn init(value: Option<Value>) -> Result<Self> {
+        let options = match value {
+            None | Some(Value::Null) => Map::new(),
+            Some(Value::Object(options)) => options,
+            Some(_) => bail!("find options must be supplied as a table"),
+        };
+        if let Some(key) = options
+            .keys()
+            .find(|key| !Self::KEYS.contains(&key.as_str()))
+        {
+            bail!(
+                "unknown find option '{}', expected one of: {}",
+                key,
+                Self::KEYS.join(", ")
+            )
+        }
+        Ok(Self { options })
+    }
+
+    fn to_find_query(&self, selector: Value) -> Result<FindQuery> {
+        let mut query = self.options.clone();
+        query.insert("selector".to_string(), selector);
+        serde_json::from_value(Value::Object(query)).context("invalid find options")
+    }
+}
+
+struct ExtensionCollectionIndexDefinition {
+    name: String,
+    ddoc: Option<String>,
+    fields: Vec<SortSpec>,
+    partial_filter_selector: Option<Value>,
+}
+
+impl ExtensionCollectionIndexDefinition {
+    const LEGACY_INDEX_NAME: &str = "extension-index";
+
+    pub fn init(value: &Value) -> Result<Self> {
+        let Some(key_values) = value.as_object() else {
+            bail!("no index definition supplied")
+        };
+        let Some(fields) = key_values.get("fields") else {
+            // a plain table of keys, kept for extensions written before named indexes existed;
+            // the keys of a table have no order, so only a single key is accepted
+            let mut keys = key_values.keys();
+            let (Some(key), None) = (keys.next(), keys.next()) else {
+                bail!("a plain table of keys can only index one field, use {{ name = \"...\", fields = {{ ... }} }} for a compound index")
+            };
+            return Ok(Self {
+                name: Self::LEGACY_INDEX_NAME.to_string(),
+                ddoc: None,
+                fields: vec![SortSpec::Simple(key.to_string())],
+                partial_filter_selector: None,
+            });
+        };
+        let Some(name) = key_values
+            .get("name")
+            .and_then(|value| value.as_str())
+            .map(|str| str.to_string())
+        else {
+            bail!("a field 'name' must be supplied")
+        };
+        let Some(fields) = fields.as_array().filter(|fields| !fields.is_empty()) else {
+            bail!("the field 'fields' must be a non-empty list")
+        };
+        let fields = fields
+            .iter()
+            .map(index_field_from)
+            .collect::<Result<Vec<SortSpec>>>()?;
+        Ok(Self {
+            name,
+            ddoc: key_values
+                .get("ddoc")
+                .and_then(|value| value.as_str())
+                .map(|str| str.to_string()),
+            fields,
+            partial_filter_selector: key_values.get("partial_filter_selector").cloned(),
+        })
+    }
+}
+
+fn index_field_from(value: &Value) -> Result<SortSpec> {
+    const INVALID_FIELD: &str = "an index field must be a name or a table like { name = \"asc\" }";
+    match value {
+        Value::String(field) => Ok(SortSpec::Simple(field.to_string())),
+        Value::Object(field) => {
+            let mut field = field.iter();
+            let (Some((name, direction)), None) = (field.next(), field.next()) else {
+                bail!(INVALID_FIELD)
+            };
+            let direction = match direction.as_str() {
+                Some("asc") => SortDirection::Asc,
+                Some("desc") => SortDirection::Desc,
+                _ => bail!("the sort direction of '{}' must be 'asc' or 'desc'", name),
+            };
+            Ok(SortSpec::Complex(HashMap::from([(
+                name.to_string(),
+                direction,
+            )])))
+        }
+        _ => bail!(INVALID_FIELD),
+    }
+}
+
+pub struct ExtensionCollectionIndex {
+    pub name: String,
+    pub ddoc: Option<String>,
+    pub index_type: String,
+    pub fields: Vec<(String, String)>,
+    pub partial_filter_selector: Option<Value>,
+}
+
+impl ExtensionCollectionIndex {
+    fn init(value: &Value) -> Result<Self> {
+        let Some(name) = value["name"].as_str() else {
+            bail!("an index without a name was returned")
+        };
+        let definition = &value["def"];
+        let fields = definition["fields"]
+            .as_array()
+            .map(|fields| {
+                fields
+                    .iter()
+                    .filter_map(|field| field.as_object())
+                    .flat_map(|field| field.iter())
+                    .map(|(name, direction)| {
+                        (
+                            name.to_string(),
+                            direction.as_str().unwrap_or("asc").to_string(),
+                        )
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let partial_filter_selector = definition
+            .get("partial_filter_selector")
+            .or(value.get("partial_filter_selector"))
+            .cloned();
+        Ok(Self {
+            name: name.to_string(),
+            ddoc: value["ddoc"].as_str().map(|ddoc| ddoc.to_string()),
+            index_type: value["type"].as_str().unwrap_or_default().to_string(),
+            fields,
+            partial_filter_selector,
+        })
+    }
+}
+
+impl fmt::Display for ExtensionCollectionIndex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let fields: Vec<String> = self
+            .fields
+            .iter()
+            .map(|(name, direction)| format!("{} {}", name, direction))
+            .collect();
+        write!(
+            f,
+            "{} ({}) [{}]",
+            self.name,
+            self.index_type,
+            fields.join(", ")
+        )
+    }
+}
+
 pub struct Document {
     pub document: Value,
 }
 
+pub struct DocumentPage {
+    pub documents: Vec<Document>,
+    pub bookmark: Option<String>,
+}
+
+#[derive(Default)]
+pub struct BulkResult {
+    pub count: usize,
+    pub failures: Vec<BulkFailure>,
+}
+
+pub struct BulkFailure {
+    pub id: String,
+    pub error: String,
+}
+
+pub struct CollectionChange {
+    pub seq: String,
+    pub id: String,
+    pub deleted: bool,
+    pub document: Option<Document>,
+}
+
+#[derive(Default)]
+pub struct ImportResult {
+    pub imported: usize,
+    pub skipped: usize,
+    pub failures: Vec<BulkFailure>,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ImportMode {
+    /// Keep existing documents and only add the missing ones
+    Merge,
+    /// Recreate the collection from the imported documents
+    Overwrite,
+}
+
+impl FromStr for ImportMode {
+    type Err = anyhow::Error;
+
+    fn from_str(mode: &str) -> Result<Self> {
+        match mode {
+            "merge" => Ok(ImportMode::Merge),
+            "overwrite" => Ok(ImportMode::Overwrite),
+            _ => bail!("unsupported import mode: '{}'", mode),
+        }
+    }
+}
+
+impl fmt::Display for ImportMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportMode::Merge => write!(f, "merge"),
+            ImportMode::Overwrite => write!(f, "overwrite"),
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct Pagination {
+    pub limit: Option<u64>,
+    pub skip: Option<u64>,
+    pub bookmark: Option<String>,
+}
+
+impl Pagination {
+    pub fn page(page: u64, page_size: u64) -> Self {
+        Self {
+            limit: Some(page_size),
+            skip: Some(page.saturating_sub(1) * page_size),
+            bookmark: None,
+        }
+    }
+
+    /// The page after the one the bookmark was returned with
+    pub fn after(bookmark: String, page_size: u64) -> Self {
+        Self {
+            limit: Some(page_size),
+            skip: None,
+            bookmark: Some(bookmark),
+        }
+    }
+
+    fn apply_to(&self, mut query: FindQuery) -> FindQuery {
+        query.limit = self.limit;
+        query.skip = self.skip;
+        query.bookmark = self.bookmark.clone();
+        query
+    }
+}
+
+pub struct WordPage {
+    pub hits: Vec<DictionaryEntry>,
+    pub bookmark: Option<String>,
+    pub total: usize,
+}
+
+impl WordPage {
+    /// Hits after this page, unknown and therefore 0 for pages of a bookmark
+    pub fn remaining(&self, pagination: &Pagination) -> usize {
+        if pagination.bookmark.is_some() {
+            return 0;
+        }
+        let seen = pagination.skip.unwrap_or_default() as usize + self.hits.len();
+        self.total.saturating_sub(seen)
+    }
+
+    /// Bookmark of the next page, unless this page is known to be the last
+    pub fn next_bookmark(&self, pagination: &Pagination) -> Option<String> {
+        let full = pagination
+            .limit
+            .is_some_and(|limit| self.hits.len() as u64 >= limit);
+        let last = pagination.bookmark.is_none() && self.remaining(pagination) == 0;
+        if full && !last {
+            self.bookmark.clone()
+        } else {
+            None
+        }
+    }
+}
+
+/// Entries of the lemmas a searched form belongs to
+#[derive(Default)]
+pub struct FormPage {
+    pub hits: Vec<DictionaryEntry>,
+    pub form_of: Vec<FormOf>,
+    pub total: usize,
+}
+
+impl FormPage {
+    pub fn remaining(&self, pagination: &Pagination) -> usize {
+        let seen = pagination.skip.unwrap_or_default() as usize + self.hits.len();
+        self.total.saturating_sub(seen)
+    }
+}
+
+/// A category, or a headword of a category, with the number of entries in it
+#[derive(Debug, PartialEq)]
+pub struct CategoryCount {
+    pub name: String,
+    pub count: u64,
+}
+
 impl DbClient {
     pub async fn init(language: Language) -> Result<Self> {
-        let client = couch_rs::Client::new(
-            &env::var("COUCH_DB_HOST").unwrap_or(env!("COUCH_DB_HOST").to_string()),
-            env!("COUCH_DB_USER"),
-            env!("COUCH_DB_PASSWORD"),
+        Self::init_with_retry_policy(language, RetryPolicy::default()).await
+    }
+
+    pub async fn init_with_retry_policy(
+        language: Language,
+        retry_policy: RetryPolicy,
+    ) -> Result<Self> {
+        let mut db_client = Self::connect_with_retry_policy(language, retry_policy)?;
+        let database_name = language.to_string();
+        let database = db_client
+            .retry(|| async { Ok(db_client.client.db(&database_name).await?) })
+            .await?;
+        db_client.database = database;
+        Ok(db_client)
+    }
+
+    /// Creates a client without contacting CouchDB, the database of the
+    /// language is neither checked nor created.
+    pub fn connect(language: Language) -> Result<Self> {
+        Self::connect_with_retry_policy(language, RetryPolicy::default())
+    }
+
+    pub fn connect_with_retry_policy(
+        language: Language,
+        retry_policy: RetryPolicy,
+    ) -> Result<Self> {
+        // couch_rs only supports timeouts in whole seconds
+        let timeout = retry_policy.timeout.as_secs().max(1);
+        let client = couch_rs::Client::new_with_timeout(
+            &Self::host(),
+            Some(env!("COUCH_DB_USER")),
+            Some(env!("COUCH_DB_PASSWORD")),
+            Some(timeout),
         )?;
-        let database = client.db(language.to_string().as_str()).await?;
+        let database = Database::new(language.to_string(), client.clone());
         Ok(Self {
             client,
             database,
             language,
+            retry_policy,
+        })
+    }
+
+    pub fn host() -> String {
+        env::var("COUCH_DB_HOST").unwrap_or(env!("COUCH_DB_HOST").to_string())
+    }
+
+    pub fn database_name(&self) -> &str {
+        self.database.name()
+    }
+
+    pub async fn server_version(&self) -> Result<String> {
+        Ok(self.client.check_status().await?.version)
+    }
+
+    pub async fn list_databases(&self) -> Result<Vec<String>> {
+        Ok(self.client.list_dbs().await?)
+    }
+
+    pub async fn has_index_on_word(&self) -> Result<bool> {
+        Ok(self
+            .database
+            .read_indexes()
+            .await?
+            .indexes
+            .iter()
+            .any(|index| index.name == WORD_INDEX_NAME))
+    }
+
+    pub async fn has_analytics(&self) -> Result<bool> {
+        let path = format!(
+            "/{}/_design/{}",
+            self.database.name(),
+            ANALYTICS_DESIGN_DOC_NAME
+        );
+        let response = self
+            .retry(|| async { Ok(self.client.req(Method::GET, &path, None).send().await?) })
+            .await?;
+        match response.status() {
+            status if status.is_success() => Ok(true),
+            StatusCode::NOT_FOUND => Ok(false),
+            status => bail!(
+                "couldn't read the design document '{}': {}",
+                ANALYTICS_DESIGN_DOC_NAME,
+                status
+            ),
+        }
+    }
+
+    pub async fn find_by_word(&self, term: &str, pagination: &Pagination) -> Result<WordPage> {
+        let query = pagination.apply_to(FindQuery::new(json!({ "word": term })));
+
+        let docs: DocumentCollection<DictionaryEntry> = self
+            .retry(|| async { Ok(self.database.find(&query).await?) })
+            .await?;
+        let hits = docs.rows;
+        let possibly_truncated = pagination
+            .limit
+            .is_some_and(|limit| hits.len() as u64 >= limit);
+        // the position of a bookmark is unknown, so the total wouldn't tell what remains
+        let counted = pagination.bookmark.is_none()
+            && (possibly_truncated || pagination.skip.unwrap_or_default() > 0);
+        let total = if counted {
+            self.count_by_word(term).await?
+        } else {
+            hits.len()
+        };
+        Ok(WordPage {
+            hits,
+            bookmark: docs.bookmark,
+            total,
         })
     }
 
-    pub async fn find_by_word(&self, term: &str) -> Result<Vec<DictionaryEntry>> {
-        let query = FindQuery::new(json!({ "word": term }));
+    /// Looks up every entry of the word, a batch at a time, where
+    /// [`DbClient::find_by_word`] stops at CouchDB's default limit of 25
+    pub async fn find_all_by_word(&self, term: &str) -> Result<Vec<DictionaryEntry>> {
+        let mut entries = Vec::new();
+        let mut pagination = Pagination::page(1, BULK_BATCH_SIZE);
+        loop {
+            let page = self.find_by_word(term, &pagination).await?;
+            let fetched = page.hits.len() as u64;
+            entries.extend(page.hits);
+            match page.bookmark {
+                Some(bookmark) if fetched == BULK_BATCH_SIZE => {
+                    pagination = Pagination::after(bookmark, BULK_BATCH_SIZE);
+                }
+                _ => return Ok(entries),
+            }
+        }
+    }
 
-        let docs: DocumentCollection<DictionaryEntry> = self.database.find(&query).await?;
-        Ok(docs.rows)
+    /// Looks up the entries of all the words with one query per batch, in no
+    /// particular order, instead of one query per word.
+    pub async fn find_by_words(&self, terms: &[String]) -> Result<Vec<DictionaryEntry>> {
+        let mut entries = Vec::new();
+        for batch in terms.chunks(BULK_BATCH_SIZE as usize) {
+            let params = QueryParams::default()
+                .keys(batch.iter().map(|term| json!(term)).collect())
+                .reduce(false)
+                .include_docs(true);
+            let view: Option<ViewCollection<Value, Value, DictionaryEntry>> = self
+                .retry(|| async {
+                    let result = self
+                        .database
+                        .query(
+                            WORDS_DESIGN_DOC_NAME,
+                            BY_WORD_VIEW_NAME,
+                            Some(params.clone()),
+                        )
+                        .await;
+                    match result {
+                        Ok(view) => Ok(Some(view)),
+                        // databases imported before words were indexed have no such view
+                        Err(err) if err.status() == Some(StatusCode::NOT_FOUND) => Ok(None),
+                        Err(err) => bail!(err),
+                    }
+                })
+                .await?;
+            match view {
+                Some(view) => entries.extend(view.rows.into_iter().filter_map(|row| row.doc)),
+                None => entries.extend(self.find_by_words_without_view(batch).await?),
+            }
+        }
+        Ok(entries)
+    }
+
+    /// `$in` can't use the index on the word, so this scans the whole database
+    async fn find_by_words_without_view(&self, terms: &[String]) -> Result<Vec<DictionaryEntry>> {
+        let mut entries = Vec::new();
+        let mut bookmark: Option<String> = None;
+        loop {
+            let mut query = FindQuery::new(json!({ "word": { "$in": terms } }));
+            query.limit = Some(BULK_BATCH_SIZE);
+            query.bookmark = bookmark.clone();
+            let docs: DocumentCollection<DictionaryEntry> = self
+                .retry(|| async { Ok(self.database.find(&query).await?) })
+                .await?;
+            let fetched = docs.rows.len() as u64;
+            entries.extend(docs.rows);
+            if fetched < BULK_BATCH_SIZE {
+                return Ok(entries);
+            }
+            bookmark = docs.bookmark;
+        }
+    }
+
+    pub async fn count_by_word(&self, term: &str) -> Result<usize> {
+        let params = QueryParams::default().key(json!(term));
+        let view: Option<ViewCollection<Value, u64, Value>> = self
+            .retry(|| async {
+                let result = self
+                    .database
+                    .query(
+                        WORDS_DESIGN_DOC_NAME,
+                        BY_WORD_VIEW_NAME,
+                        Some(params.clone()),
+                    )
+                    .await;
+                match result {
+                    Ok(view) => Ok(Some(view)),
+                    // databases imported before words were indexed have no such view
+                    Err(err) if err.status() == Some(StatusCode::NOT_FOUND) => Ok(None),
+                    Err(err) => bail!(err),
+                }
+            })
+            .await?;
+        match view {
+            Some(view) => Ok(view.rows.first().map_or(0, |row| row.value as usize)),
+            None => {
+                self.retry(|| count_matching(&self.database, json!({ "word": term })))
+                    .await
+            }
+        }
+    }
+
+    /// Looks up the entries hyphenated into the number of syllables, only those
+    /// of the term when one is given.
+    pub async fn find_by_syllable_count(
+        &self,
+        count: u64,
+        term: Option<&str>,
+        pagination: &Pagination,
+    ) -> Result<WordPage> {
+        let range = |params: QueryParams<Value>| match term {
+            Some(term) => params.key(json!([count, term])),
+            None => params.start_key(json!([count])).end_key(json!([count, {}])),
+        };
+        let mut params = range(QueryParams::default())
+            .include_docs(true)
+            .reduce(false);
+        if let Some(skip) = pagination.skip {
+            params = params.skip(skip);
+        }
+        if let Some(limit) = pagination.limit {
+            params = params.limit(limit);
+        }
+        let view: ViewCollection<Value, u64, DictionaryEntry> =
+            self.query_syllables(params).await?;
+        let hits: Vec<DictionaryEntry> = view.rows.into_iter().filter_map(|row| row.doc).collect();
+        let possibly_truncated = pagination
+            .limit
+            .is_some_and(|limit| hits.len() as u64 >= limit);
+        let total = if possibly_truncated || pagination.skip.unwrap_or_default() > 0 {
+            let count: ViewCollection<Value, u64, Value> =
+                self.query_syllables(range(QueryParams::default())).await?;
+            count.rows.first().map_or(0, |row| row.value as usize)
+        } else {
+            hits.len()
+        };
+        Ok(WordPage {
+            hits,
+            bookmark: None,
+            total,
+        })
+    }
+
+    async fn query_syllables<V, D>(
+        &self,
+        params: QueryParams<Value>,
+    ) -> Result<ViewCollection<Value, V, D>>
+    where
+        V: DeserializeOwned,
+        D: TypedCouchDocument,
+    {
+        self.retry(|| async {
+            let result = self
+                .database
+                .query(
+                    SYLLABLES_DESIGN_DOC_NAME,
+                    BY_SYLLABLE_COUNT_VIEW_NAME,
+                    Some(params.clone()),
+                )
+                .await;
+            match result {
+                Ok(view) => Ok(view),
+                Err(err) if err.status() == Some(StatusCode::NOT_FOUND) => bail!(
+                    "the dictionary of language {} has no syllable index, import it again with --force",
+                    self.language
+                ),
+                Err(err) => bail!(err),
+            }
+        })
+        .await
+    }
+
+    /// Looks up the entries listing the term as one of their inflected forms,
+    /// e.g. "dictionaries" resolves to the plural of "dictionary".
+    pub async fn find_by_form(&self, term: &str, pagination: &Pagination) -> Result<FormPage> {
+        let mut params = QueryParams::default()
+            .key(term.to_string())
+            .include_docs(true)
+            .reduce(false);
+        if let Some(skip) = pagination.skip {
+            params = params.skip(skip);
+        }
+        if let Some(limit) = pagination.limit {
+            params = params.limit(limit);
+        }
+        let Some(view) = self
+            .query_forms::<Vec<Vec<String>>, DictionaryEntry>(params)
+            .await?
+        else {
+            return Ok(FormPage::default());
+        };
+
+        let mut form_of: Vec<FormOf> = Vec::new();
+        let mut hits: Vec<DictionaryEntry> = Vec::new();
+        for item in view.rows {
+            let Some(entry) = item.doc else {
+                continue;
+            };
+            // an entry lists a form once per inflection it has, e.g. "read" as past and participle
+            for tags in item.value {
+                let resolved = FormOf {
+                    form: item.key.clone(),
+                    tags,
+                    lemma: entry.word.clone(),
+                };
+                if !form_of.contains(&resolved) {
+                    form_of.push(resolved);
+                }
+            }
+            hits.push(entry);
+        }
+        let possibly_truncated = pagination
+            .limit
+            .is_some_and(|limit| hits.len() as u64 >= limit);
+        let total = if possibly_truncated || pagination.skip.unwrap_or_default() > 0 {
+            let count = self
+                .query_forms::<u64, Value>(QueryParams::default().key(term.to_string()))
+                .await?;
+            count
+                .and_then(|view| view.rows.first().map(|row| row.value as usize))
+                .unwrap_or_default()
+        } else {
+            hits.len()
+        };
+        Ok(FormPage {
+            hits,
+            form_of,
+            total,
+        })
+    }
+
+    async fn query_forms<V, D>(
+        &self,
+        params: QueryParams<String>,
+    ) -> Result<Option<ViewCollection<String, V, D>>>
+    where
+        V: DeserializeOwned,
+        D: TypedCouchDocument,
+    {
+        self.retry(|| async {
+            let result = self
+                .database
+                .query(
+                    FORMS_DESIGN_DOC_NAME,
+                    BY_FORM_VIEW_NAME,
+                    Some(params.clone()),
+                )
+                .await;
+            match result {
+                Ok(view) => Ok(Some(view)),
+                // databases imported before forms were indexed have no such view
+                Err(err) if err.status() == Some(StatusCode::NOT_FOUND) => Ok(None),
+                Err(err) => bail!(err),
+            }
+        })
+        .await
+    }
+
+    /// Names of the categories containing the term, ignoring case
+    /// The categories are read a batch at a time, only the matching ones are kept.
+    pub async fn search_categories(&self, term: &str) -> Result<Vec<CategoryCount>> {
+        let term = term.to_lowercase();
+        let mut found = Vec::new();
+        let mut start_key: Option<Value> = None;
+        loop {
+            let mut params = QueryParams::default()
+                .group_level(1)
+                .limit(BULK_BATCH_SIZE + 1);
+            if let Some(start_key) = start_key {
+                params = params.start_key(start_key);
+            }
+            let mut categories = self.query_categories(params).await?;
+            let next = if categories.len() as u64 > BULK_BATCH_SIZE {
+                categories.pop()
+            } else {
+                None
+            };
+            found.extend(
+                categories
+                    .into_iter()
+                    .filter(|category| category.name.to_lowercase().contains(&term)),
+            );
+            match next {
+                Some(next) => start_key = Some(json!([next.name])),
+                None => return Ok(found),
+            }
+        }
+    }
+
+    /// Headwords of the category with their number of entries
+    pub async fn category_members(&self, category: &str) -> Result<Vec<CategoryCount>> {
+        let params = QueryParams::default()
+            .start_key(json!([category]))
+            .end_key(json!([category, {}]))
+            .group_level(2);
+        self.query_categories(params).await
+    }
+
+    /// Keys of the category view are `[category, word]`, the last key
+    /// of each group is used as its name.
+    async fn query_categories(&self, params: QueryParams<Value>) -> Result<Vec<CategoryCount>> {
+        let view: ViewCollection<Value, u64, Value> = self
+            .retry(|| async {
+                let result = self
+                    .database
+                    .query(
+                        CATEGORIES_DESIGN_DOC_NAME,
+                        BY_CATEGORY_VIEW_NAME,
+                        Some(params.clone()),
+                    )
+                    .await;
+                match result {
+                    Ok(view) => Ok(view),
+                    Err(err) if err.status() == Some(StatusCode::NOT_FOUND) => bail!(
+                        "the dictionary of language {} has no category index, import it again with --force",
+                        self.language
+                    ),
+                    Err(err) => bail!(err),
+                }
+            })
+            .await?;
+        Ok(view
+            .rows
+            .into_iter()
+            .filter_map(|item| {
+                let name = item.key.as_array()?.last()?.as_str()?.to_string();
+                Some(CategoryCount {
+                    name,
+                    count: item.value,
+                })
+            })
+            .collect())
+    }
+
+    async fn extension_db(&self, extension_name: &str) -> Result<Database> {
+        let database_name = extension_database!(self.language, extension_name).to_string();
+        self.retry(|| async { Ok(self.client.db(&database_name).await?) })
+            .await
+    }
+
+    /// Runs an idempotent operation again, after a growing delay, as long as
+    /// it fails because CouchDB is unreachable, timed out or overloaded.
+    async fn retry<T, F, Fut>(&self, operation: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        self.retry_policy.retry_async(is_transient, operation).await
     }
 
     async fn find_raw_in_extension_collection(
         &self,
         extension_name: &str,
         query: Document,
-    ) -> Result<Vec<Value>> {
-        let extension_db = self
-            .client
-            .db(extension_database!(self.language, extension_name))
-            .await?;
+        options: ExtensionCollectionFindOptions,
+    ) -> Result<(Vec<Value>, Option<String>)> {
+        let extension_db = self.extension_db(extension_name).await?;
+
+        let mut find_query = options.to_find_query(query.document)?;
+        if find_query.limit.is_some() {
+            let query_result = extension_db.find_raw(&find_query).await?;
+            return Ok((query_result.rows, query_result.bookmark));
+        }
 
         let mut result: Vec<Value> = Vec::new();
-        let mut bookmark: Option<String> = None;
-        let mut find_query = FindQuery::new(query.document);
+        let mut bookmark: Option<String> = find_query.bookmark.take();
 
         loop {
             find_query.bookmark = bookmark;
@@ -133,21 +918,27 @@ impl DbClient {
             }
             result.extend(page);
             bookmark = query_result.bookmark;
+            // skip is relative to the bookmark, so it only applies to the first page
+            find_query.skip = None;
         }
-        Ok(result)
+        Ok((result, None))
     }
 
     pub async fn find_in_extension_collection(
         &self,
         extension_name: &str,
         query: Document,
-    ) -> Result<Vec<Document>> {
-        Ok(self
-            .find_raw_in_extension_collection(extension_name, query)
-            .await?
-            .into_iter()
-            .map(Document::from)
-            .collect())
+        options: Option<Document>,
+    ) -> Result<DocumentPage> {
+        let options =
+            ExtensionCollectionFindOptions::init(options.map(|options| options.document))?;
+        let (documents, bookmark) = self
+            .find_raw_in_extension_collection(extension_name, query, options)
+            .await?;
+        Ok(DocumentPage {
+            documents: documents.into_iter().map(Document::from).collect(),
+            bookmark,
+        })
     }
 
     pub async fn find_one_in_extension_collection(
@@ -155,10 +946,7 @@ impl DbClient {
         extension_name: &str,
         document: Document,
     ) -> Result<Option<Document>> {
-        let extension_db = self
-            .client
-            .db(extension_database!(self.language, extension_name))
-            .await?;
+        let extension_db = self.extension_db(extension_name).await?;
         let result = extension_db
             .find_raw(&FindQuery::new(document.document).limit(1))
             .await?;
@@ -171,10 +959,7 @@ impl DbClient {
         extension_name: &str,
         mut document: Document,
     ) -> Result<Document> {
-        let extension_db = self
-            .client
-            .db(extension_database!(self.language, extension_name))
-            .await?;
+        let extension_db = self.extension_db(extension_name).await?;
         let result = extension_db.create(&mut document.document).await?;
         Ok(Document::from(json!({"_id": result.id})))
     }
@@ -184,32 +969,103 @@ impl DbClient {
         extension_name: &str,
         mut document: Document,
     ) -> Result<Document> {
-        let extension_db = self
-            .client
-            .db(extension_database!(self.language, extension_name))
-            .await?;
+        let extension_db = self.extension_db(extension_name).await?;
         let result = extension_db.save(&mut document.document).await?;
         Ok(Document::from(json!({"_rev": result.rev})))
     }
 
-    // TODO this will query all documents and leads to bad performance
-    pub async fn delete_many_in_extension_collection(
+    pub async fn count_in_extension_collection(
         &self,
         extension_name: &str,
         query: Document,
     ) -> Result<usize> {
-        let mut documents = self
-            .find_raw_in_extension_collection(extension_name, query)
-            .await?;
-        let extension_db = self
-            .client
-            .db(extension_database!(self.language, extension_name))
-            .await?;
-        for document in &mut documents {
+        let extension_db = self.extension_db(extension_name).await?;
+        count_matching(&extension_db, query.document).await
+    }
+
+    pub async fn delete_many_in_extension_collection(
+        &self,
+        extension_name: &str,
+        query: Document,
+    ) -> Result<BulkResult> {
+        let fields = vec!["_id".to_string(), "_rev".to_string()];
+        self.bulk_modify_in_extension_collection(extension_name, query, Some(fields), |document| {
             document["_deleted"] = json!(true);
+        })
+        .await
+    }
+
+    pub async fn update_many_in_extension_collection(
+        &self,
+        extension_name: &str,
+        query: Document,
+        patch: Document,
+    ) -> Result<BulkResult> {
+        let Some(patch) = patch.document.as_object().cloned() else {
+            bail!("the patch must be an object")
+        };
+        self.bulk_modify_in_extension_collection(extension_name, query, None, |document| {
+            for (key, value) in &patch {
+                if key != "_id" && key != "_rev" {
+                    document[key] = value.clone();
+                }
+            }
+        })
+        .await
+    }
+
+    async fn bulk_modify_in_extension_collection(
+        &self,
+        extension_name: &str,
+        query: Document,
+        fields: Option<Vec<String>>,
+        modify: impl Fn(&mut Value),
+    ) -> Result<BulkResult> {
+        let extension_db = self.extension_db(extension_name).await?;
+
+        // modified documents can move between the pages of the query, so the
+        // ids are collected before any document is modified
+        let mut ids: Vec<String> = Vec::new();
+        let mut bookmark: Option<String> = None;
+        let mut id_query = FindQuery::new(query.document)
+            .fields(vec!["_id".to_string()])
+            .limit(BULK_BATCH_SIZE);
+        loop {
+            id_query.bookmark = bookmark;
+            let query_result = extension_db.find_raw(&id_query).await?;
+            if query_result.rows.is_empty() {
+                break;
+            }
+            ids.extend(
+                query_result
+                    .rows
+                    .iter()
+                    .filter_map(|row| row["_id"].as_str().map(String::from)),
+            );
+            bookmark = query_result.bookmark;
+        }
+
+        let mut bulk_result = BulkResult::default();
+        for chunk in ids.chunks(BULK_BATCH_SIZE as usize) {
+            let mut find_query =
+                FindQuery::new(json!({ "_id": { "$in": chunk } })).limit(BULK_BATCH_SIZE);
+            find_query.fields = fields.clone();
+            let mut batch: Vec<Value> = extension_db.find_raw(&find_query).await?.rows;
+            for document in &mut batch {
+                modify(document);
+            }
+            let results = extension_db.bulk_docs(&mut batch).await?;
+            for (document, result) in batch.iter().zip(results) {
+                match result {
+                    Ok(_) => bulk_result.count += 1,
+                    Err(error) => bulk_result.failures.push(BulkFailure {
+                        id: document["_id"].as_str().unwrap_or_default().to_string(),
+                        error: error.to_string(),
+                    }),
+                }
+            }
         }
-        extension_db.bulk_docs(&mut documents).await?;
-        Ok(documents.len())
+        Ok(bulk_result)
     }
 
     pub async fn get_view_in_extension_collection(
@@ -217,10 +1073,7 @@ impl DbClient {
         extension_name: &str,
         view: Document,
     ) -> Result<Document> {
-        let extension_db = self
-            .client
-            .db(extension_database!(self.language, extension_name))
-            .await?;
+        let extension_db = self.extension_db(extension_name).await?;
 
         let view_definition = ExtensionCollectionViewDefinition::init(&view.document)?;
 
@@ -257,10 +1110,7 @@ impl DbClient {
         extension_name: &str,
         definition: Document,
     ) -> Result<Document> {
-        let extension_db = self
-            .client
-            .db(extension_database!(self.language, extension_name))
-            .await?;
+        let extension_db = self.extension_db(extension_name).await?;
 
         let view_definition = ExtensionCollectionViewDefinition::init(&definition.document)?;
         let Some(key_values) = definition.document.as_object() else {
@@ -295,36 +1145,262 @@ impl DbClient {
     pub async fn create_index_for_extension_collection(
         &self,
         extension_name: &str,
-        keys: Document,
-    ) -> Result<()> {
-        let extension_db = self
+        definition: Document,
+    ) -> Result<Document> {
+        let extension_db = self.extension_db(extension_name).await?;
+        let index_definition = ExtensionCollectionIndexDefinition::init(&definition.document)?;
+
+        let mut index = json!({ "fields": index_definition.fields });
+        if let Some(selector) = index_definition.partial_filter_selector {
+            index["partial_filter_selector"] = selector;
+        }
+        let mut body = json!({
+            "name": index_definition.name,
+            "type": "json",
+            "index": index,
+        });
+        if let Some(ddoc) = index_definition.ddoc {
+            body["ddoc"] = json!(ddoc);
+        }
+
+        let response = self
             .client
-            .db(extension_database!(self.language, extension_name))
+            .req(
+                Method::POST,
+                &format!("/{}/_index", extension_db.name()),
+                None,
+            )
+            .body(body.to_string())
+            .send()
             .await?;
-        let mut fields = Vec::new();
+        let result: Value = response.json().await?;
+        if let Some(error) = result.get("error") {
+            bail!(
+                "couldn't create index '{}': {} {}",
+                index_definition.name,
+                error,
+                result.get("reason").unwrap_or(&Value::Null)
+            );
+        }
+        Ok(Document::from(json!({
+            "created": result["result"] == "created",
+            "id": result["id"],
+            "name": result["name"],
+        })))
+    }
 
-        if let Some(key_values) = keys.document.as_object() {
-            for (key, _) in key_values {
-                fields.push(SortSpec::Simple(key.to_string()));
+    pub async fn list_indexes_in_extension_collection(
+        &self,
+        extension_name: &str,
+    ) -> Result<Vec<ExtensionCollectionIndex>> {
+        let extension_db = self.extension_db(extension_name).await?;
+        let path = format!("/{}/_index", extension_db.name());
+        let result: Value = self
+            .retry(|| async {
+                let response = self.client.req(Method::GET, &path, None).send().await?;
+                Ok(response.json().await?)
+            })
+            .await?;
+        let Some(indexes) = result["indexes"].as_array() else {
+            bail!(
+                "couldn't list indexes for extension collection '{}'",
+                extension_name
+            )
+        };
+        indexes.iter().map(ExtensionCollectionIndex::init).collect()
+    }
+
+    pub async fn drop_index_in_extension_collection(
+        &self,
+        extension_name: &str,
+        index_name: &str,
+    ) -> Result<usize> {
+        let extension_db = self.extension_db(extension_name).await?;
+        let mut dropped = 0;
+        for index in self
+            .list_indexes_in_extension_collection(extension_name)
+            .await?
+        {
+            if let (Some(ddoc), true) = (index.ddoc, index.name == index_name) {
+                let deleted = self
+                    .retry(|| async {
+                        Ok(extension_db
+                            .delete_index(ddoc.clone(), index.name.clone())
+                            .await?)
+                    })
+                    .await?;
+                if deleted {
+                    dropped += 1;
+                }
             }
-        } else {
-            bail!("no index keys supplied.")
         }
+        Ok(dropped)
+    }
 
-        let index_def = IndexFields { fields };
-        let result = extension_db
-            .insert_index("extension-index", index_def, None, None)
+    pub async fn list_extension_collections(&self) -> Result<Vec<String>> {
+        let prefix = extension_database!(self.language, "").to_string();
+        Ok(self
+            .client
+            .list_dbs()
+            .await?
+            .into_iter()
+            .filter_map(|name| name.strip_prefix(&prefix).map(|name| name.to_string()))
+            .collect())
+    }
+
+    /// Streams the documents of an extension collection without their revisions,
+    /// fetching them a batch at a time
+    pub async fn export_extension_collection(
+        &self,
+        extension_name: &str,
+    ) -> Result<impl Stream<Item = Result<Value>>> {
+        let extension_db = self.extension_db(extension_name).await?;
+        let path = format!("/{}/_all_docs", extension_db.name());
+        let client = self.clone();
+
+        // the state is the key to start the next batch at, None once all are fetched
+        let batches = stream::try_unfold(Some(None), move |start_key: Option<Option<String>>| {
+            let client = client.clone();
+            let path = path.clone();
+            async move {
+                let Some(start_key) = start_key else {
+                    return anyhow::Ok(None);
+                };
+                let (documents, next_key) = client.export_batch(&path, start_key).await?;
+                Ok(Some((documents, next_key.map(Some))))
+            }
+        });
+        Ok(batches
+            .map_ok(|documents| stream::iter(documents.into_iter().map(Ok)))
+            .try_flatten())
+    }
+
+    async fn export_batch(
+        &self,
+        path: &str,
+        start_key: Option<String>,
+    ) -> Result<(Vec<Value>, Option<String>)> {
+        let mut options = HashMap::from([
+            ("include_docs".to_string(), "true".to_string()),
+            ("limit".to_string(), (BULK_BATCH_SIZE + 1).to_string()),
+        ]);
+        if let Some(start_key) = &start_key {
+            options.insert("startkey".to_string(), json!(start_key).to_string());
+        }
+        let response = self
+            .client
+            .req(Method::GET, path, Some(&options))
+            .send()
             .await?;
-        if let Some(error) = result.error {
-            bail!(error);
+        let mut result: Value = response.json().await?;
+        let Some(rows) = result["rows"].as_array_mut() else {
+            bail!("couldn't export the documents of '{}'", path)
+        };
+        let next_key = if rows.len() as u64 > BULK_BATCH_SIZE {
+            rows.pop()
+                .and_then(|row| row["id"].as_str().map(|id| id.to_string()))
+        } else {
+            None
+        };
+        let documents = rows
+            .iter_mut()
+            .filter_map(|row| row["doc"].as_object_mut())
+            .map(|document| {
+                document.remove("_rev");
+                Value::Object(std::mem::take(document))
+            })
+            .collect();
+        Ok((documents, next_key))
+    }
+
+    pub async fn import_into_extension_collection(
+        &self,
+        extension_name: &str,
+        mut documents: Vec<Value>,
+        mode: &ImportMode,
+    ) -> Result<ImportResult> {
+        for (i, document) in documents.iter_mut().enumerate() {
+            let Some(document) = document.as_object_mut() else {
+                bail!(
+                    "document {} of the import into '{}' isn't an object",
+                    i,
+                    extension_name
+                )
+            };
+            if document.get("_id").is_some_and(|id| !id.is_string()) {
+                bail!(
+                    "document {} of the import into '{}' has an '_id' that isn't a string",
+                    i,
+                    extension_name
+                )
+            }
+            document.remove("_rev");
         }
-        Ok(())
+        // the documents are valid, only now the existing collection may be replaced
+        let database_name = extension_database!(self.language, extension_name).to_string();
+        if *mode == ImportMode::Overwrite {
+            self.client.destroy_db(&database_name).await?;
+        }
+        let extension_db = self.client.db(&database_name).await?;
+
+        let mut import_result = ImportResult::default();
+        for chunk in documents.chunks_mut(BULK_BATCH_SIZE as usize) {
+            let results = extension_db.bulk_docs(chunk).await?;
+            for (document, result) in chunk.iter().zip(results) {
+                match result {
+                    Ok(_) => import_result.imported += 1,
+                    Err(error) if error.status() == Some(StatusCode::CONFLICT) => {
+                        import_result.skipped += 1
+                    }
+                    Err(error) => import_result.failures.push(BulkFailure {
+                        id: document["_id"].as_str().unwrap_or_default().to_string(),
+                        error: error.to_string(),
+                    }),
+                }
+            }
+        }
+        Ok(import_result)
+    }
+
+    /// Streams the changes of an extension collection, starting after `since`.
+    /// `since` is either a sequence returned by an earlier change, "0" to
+    /// replay the whole collection or "now" to only receive new changes.
+    pub async fn watch_extension_collection(
+        &self,
+        extension_name: &str,
+        since: &str,
+    ) -> Result<impl Stream<Item = Result<CollectionChange>>> {
+        let extension_db = self.extension_db(extension_name).await?;
+        let since = match since {
+            // the changes stream quotes the sequence, which "now" doesn't support
+            "now" => self.client.get_info(extension_db.name()).await?.update_seq,
+            since => since.to_string(),
+        };
+
+        let mut changes = extension_db.changes(Some(Value::String(since)));
+        changes.set_infinite(true);
+        Ok(changes.map(|change| {
+            let change = change?;
+            Ok(CollectionChange {
+                seq: match change.seq {
+                    Value::String(seq) => seq,
+                    seq => seq.to_string(),
+                },
+                id: change.id,
+                deleted: change.deleted,
+                document: change.doc.map(Document::from),
+            })
+        }))
     }
 
     pub async fn word_document_count(&self) -> Result<u64> {
         let result = self
-            .database
-            .query_raw(ANALYTICS_DESIGN_DOC_NAME, WORD_COUNT_VIEW_NAME, None)
+            .retry(|| async {
+                Ok(self
+                    .database
+                    .query_raw(ANALYTICS_DESIGN_DOC_NAME, WORD_COUNT_VIEW_NAME, None)
+                    .await?)
+            })
             .await?;
         Ok(word_count_view_as_u64(result))
     }
@@ -359,6 +1435,10 @@ impl DbClient {
         }
         self.create_index_on_word().await?;
         self.create_analytics().await?;
+        self.create_forms_index().await?;
+        self.create_categories_index().await?;
+        self.create_words_index().await?;
+        self.create_syllables_index().await?;
 
         let mut total_count = 0;
 
@@ -367,16 +1447,55 @@ impl DbClient {
             let result = self.database.bulk_docs(chunk).await?;
             total_count += result.len();
         }
+        self.write_import_stamp(total_count).await?;
 
         Ok(total_count)
     }
+
+    /// Identifies the last import of the language, `None` if the database
+    /// was never imported. A new import always changes the stamp.
+    pub async fn import_stamp(&self) -> Result<Option<String>> {
+        Ok(self
+            .read_local_document(IMPORT_STAMP_DOC_NAME)
+            .await?
+            .map(|stamp| format!("{}-{}", stamp["imported_at"], stamp["entries"])))
+    }
+
+    async fn write_import_stamp(&self, entries: usize) -> Result<()> {
+        let imported_at = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis();
+        let mut stamp = json!({"imported_at": imported_at.to_string(), "entries": entries});
+        if let Some(existing) = self.read_local_document(IMPORT_STAMP_DOC_NAME).await? {
+            stamp["_rev"] = existing["_rev"].clone();
+        }
+        let path = format!("/{}/_local/{}", self.database.name(), IMPORT_STAMP_DOC_NAME);
+        self.client
+            .req(Method::PUT, &path, None)
+            .body(stamp.to_string())
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+
+    /// Local documents are neither replicated nor returned by queries.
+    async fn read_local_document(&self, name: &str) -> Result<Option<Value>> {
+        let path = format!("/{}/_local/{}", self.database.name(), name);
+        let response = self
+            .retry(|| async { Ok(self.client.req(Method::GET, &path, None).send().await?) })
+            .await?;
+        match response.status() {
+            StatusCode::NOT_FOUND => Ok(None),
+            _ => Ok(Some(response.error_for_status()?.json().await?)),
+        }
+    }
+
     pub async fn create_index_on_word(&self) -> Result<bool> {
         let index_def = IndexFields {
             fields: vec![SortSpec::Simple("word".to_string())],
         };
         let result = self
             .database
-            .insert_index("word-index", index_def, None, None)
+            .insert_index(WORD_INDEX_NAME, index_def, None, None)
             .await?;
         Ok(result.result.is_some())
     }
@@ -404,6 +1523,74 @@ impl DbClient {
             Err(error) => bail!(error),
         }
     }
+
+    pub async fn create_forms_index(&self) -> Result<bool> {
+        let definitions = CouchViews::new(BY_FORM_VIEW_NAME, forms_function());
+        let result = self
+            .database
+            .create_view(FORMS_DESIGN_DOC_NAME, definitions)
+            .await;
+        match result {
+            Ok(_) => Ok(true),
+            Err(CouchError::OperationFailed(ErrorDetails {
+                id: _,
+                status: StatusCode::CONFLICT,
+                ..
+            })) => Ok(false),
+            Err(error) => bail!(error),
+        }
+    }
+
+    pub async fn create_words_index(&self) -> Result<bool> {
+        let definitions = CouchViews::new(BY_WORD_VIEW_NAME, words_function());
+        let result = self
+            .database
+            .create_view(WORDS_DESIGN_DOC_NAME, definitions)
+            .await;
+        match result {
+            Ok(_) => Ok(true),
+            Err(CouchError::OperationFailed(ErrorDetails {
+                id: _,
+                status: StatusCode::CONFLICT,
+                ..
+            })) => Ok(false),
+            Err(error) => bail!(error),
+        }
+    }
+
+    pub async fn create_syllables_index(&self) -> Result<bool> {
+        let definitions = CouchViews::new(BY_SYLLABLE_COUNT_VIEW_NAME, syllables_function());
+        let result = self
+            .database
+            .create_view(SYLLABLES_DESIGN_DOC_NAME, definitions)
+            .await;
+        match result {
+            Ok(_) => Ok(true),
+            Err(CouchError::OperationFailed(ErrorDetails {
+                id: _,
+                status: StatusCode::CONFLICT,
+                ..
+            })) => Ok(false),
+            Err(error) => bail!(error),
+        }
+    }
+
+    pub async fn create_categories_index(&self) -> Result<bool> {
+        let definitions = CouchViews::new(BY_CATEGORY_VIEW_NAME, categories_function());
+        let result = self
+            .database
+            .create_view(CATEGORIES_DESIGN_DOC_NAME, definitions)
+            .await;
+        match result {
+            Ok(_) => Ok(true),
+            Err(CouchError::OperationFailed(ErrorDetails {
+                id: _,
+                status: StatusCode::CONFLICT,
+                ..
+            })) => Ok(false),
+            Err(error) => bail!(error),
+        }
+    }
 }
 
 pub fn word_count_view_as_u64(view_collection: ViewCollection<Value, Value, Value>) -> u64 {
@@ -416,6 +1603,43 @@ pub fn word_count_view_as_u64(view_collection: ViewCollection<Value, Value, Valu
     maybe_count.unwrap_or_default()
 }
 
+async fn count_matching(database: &Database, selector: Value) -> Result<usize> {
+    let mut count = 0;
+    let mut bookmark: Option<String> = None;
+    let mut find_query = FindQuery::new(selector)
+        .fields(vec!["_id".to_string()])
+        .limit(BULK_BATCH_SIZE);
+
+    loop {
+        find_query.bookmark = bookmark;
+        let query_result = database.find_raw(&find_query).await?;
+        if query_result.rows.is_empty() {
+            break;
+        }
+        count += query_result.rows.len();
+        bookmark = query_result.bookmark;
+    }
+    Ok(count)
+}
+
+fn is_transient(err: &anyhow::Error) -> bool {
+    if let Some(err) = err.downcast_ref::<reqwest::Error>() {
+        return err.is_connect()
+            || err.is_timeout()
+            || err.status().is_some_and(is_transient_status);
+    }
+    // couch_rs reports connection failures and timeouts as 501
+    err.downcast_ref::<CouchError>()
+        .and_then(|err| err.status())
+        .is_some_and(is_transient_status)
+}
+
+fn is_transient_status(status: StatusCode) -> bool {
+    status.is_server_error()
+        || status == StatusCode::REQUEST_TIMEOUT
+        || status == StatusCode::TOO_MANY_REQUESTS
+}
+
 fn count_words_function() -> CouchFunc {
     CouchFunc {
         map: "function(doc) { doc.word && emit(doc._id, 1); }".to_string(),
@@ -423,6 +1647,74 @@ fn count_words_function() -> CouchFunc {
     }
 }
 
+/// Counts the entries of every word
+fn words_function() -> CouchFunc {
+    CouchFunc {
+        map: "function(doc) { doc.word && emit(doc.word, 1); }".to_string(),
+        reduce: Some("_count".to_string()),
+    }
+}
+
+/// Emits every inflected form of an entry once with the tags of each of its
+/// inflections, forms with one of [`NON_FORM_TAGS`] and forms equal to the
+/// word itself are skipped. Entries are counted per form.
+fn forms_function() -> CouchFunc {
+    CouchFunc {
+        map: format!(
+            r#"function(doc) {{
+            if (!doc.word || !doc.forms) {{ return; }}
+            var skipped = {};
+            var tags_by_form = {{}};
+            doc.forms.forEach(function(form) {{
+                var tags = form.tags || [];
+                var describes_table = tags.some(function(tag) {{ return skipped.indexOf(tag) >= 0; }});
+                if (form.form && form.form !== doc.word && !describes_table) {{
+                    tags_by_form[form.form] = (tags_by_form[form.form] || []).concat([tags]);
+                }}
+            }});
+            Object.keys(tags_by_form).forEach(function(form) {{ emit(form, tags_by_form[form]); }});
+        }}"#,
+            json!(NON_FORM_TAGS)
+        ),
+        reduce: Some("_count".to_string()),
+    }
+}
+
+/// Emits every number of syllables an entry is hyphenated into once with the
+/// word, counting the entries per number of syllables and per word.
+fn syllables_function() -> CouchFunc {
+    CouchFunc {
+        map: r#"function(doc) {
+            if (!doc.word || !doc.hyphenations) { return; }
+            var counts = {};
+            doc.hyphenations.forEach(function(hyphenation) {
+                var parts = hyphenation.parts || [];
+                if (parts.length > 0) { counts[parts.length] = true; }
+            });
+            Object.keys(counts).forEach(function(count) { emit([Number(count), doc.word], 1); });
+        }"#
+        .to_string(),
+        reduce: Some("_count".to_string()),
+    }
+}
+
+/// Emits every category of an entry and of its senses once with the word,
+/// counting the entries per category and per word.
+fn categories_function() -> CouchFunc {
+    CouchFunc {
+        map: r#"function(doc) {
+            if (!doc.word) { return; }
+            var names = {};
+            var add = function(category) { if (category.name) { names[category.name] = true; } };
+            (doc.categories || []).forEach(add);
+            (doc.senses || []).forEach(function(sense) { (sense.categories || []).forEach(add); });
+            Object.keys(names).forEach(function(name) { emit([name, doc.word], 1); });
+        }"#
+        .to_string(),
+        reduce: Some("_count".to_string()),
+    }
+}
+
 fn custom_couch_function(map: &str, reduce: Option<&str>) -> CouchFunc {
     CouchFunc {
         map: map.to_string(),
@@ -1,7 +1,15 @@
 use anyhow;
-use mlua::{Error, FromLua, IntoLua, Lua, LuaSerdeExt, Result, UserData, UserDataMethods, Value};
+use futures_util::StreamExt;
+use mlua::{
+    Error, FromLua, Function, IntoLua, IntoLuaMulti, Lua, LuaSerdeExt, MultiValue, Result,
+    UserData, UserDataMethods, Value,
+};
+use serde_json::json;
+use std::pin::pin;
 
-use crate::client::{DbClientMutex, Document};
+use crate::client::{
+    BulkResult, CollectionChange, DbClientMutex, Document, ExtensionCollectionIndex,
+};
 
 fn ok_or_runtime_error<T>(result: anyhow::Result<T>) -> Result<T> {
     result.map_err(|err| Error::RuntimeError(err.to_string()))
@@ -11,13 +19,20 @@ impl UserData for DbClientMutex {
     fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
         methods.add_async_method(
             "find_in_collection",
-            async |_, this, (extension_name, document): (String, Document)| {
+            async |_,
+                   this,
+                   (extension_name, document, options): (
+                String,
+                Document,
+                Option<Document>,
+            )| {
                 let db_client = &this.client.lock().await;
-                ok_or_runtime_error(
+                let page = ok_or_runtime_error(
                     db_client
-                        .find_in_extension_collection(&extension_name, document)
+                        .find_in_extension_collection(&extension_name, document, options)
                         .await,
-                )
+                )?;
+                Ok((page.documents, page.bookmark))
             },
         );
         methods.add_async_method(
@@ -64,6 +79,28 @@ impl UserData for DbClientMutex {
                 )
             },
         );
+        methods.add_async_method(
+            "update_many_in_collection",
+            async |_, this, (extension_name, query, patch): (String, Document, Document)| {
+                let db_client = &this.client.lock().await;
+                ok_or_runtime_error(
+                    db_client
+                        .update_many_in_extension_collection(&extension_name, query, patch)
+                        .await,
+                )
+            },
+        );
+        methods.add_async_method(
+            "count_in_collection",
+            async |_, this, (extension_name, query): (String, Document)| {
+                let db_client = &this.client.lock().await;
+                ok_or_runtime_error(
+                    db_client
+                        .count_in_extension_collection(&extension_name, query)
+                        .await,
+                )
+            },
+        );
         methods.add_async_method(
             "get_view_in_collection",
             async |_, this, (extension_name, view): (String, Document)| {
@@ -77,15 +114,63 @@ impl UserData for DbClientMutex {
         );
         methods.add_async_method(
             "create_index_for_collection",
-            async |_, this, (extension_name, keys): (String, Document)| {
+            async |_, this, (extension_name, definition): (String, Document)| {
                 let db_client = &this.client.lock().await;
                 ok_or_runtime_error(
                     db_client
-                        .create_index_for_extension_collection(&extension_name, keys)
+                        .create_index_for_extension_collection(&extension_name, definition)
                         .await,
                 )
             },
         );
+        methods.add_async_method(
+            "list_indexes_in_collection",
+            async |_, this, extension_name: String| {
+                let db_client = &this.client.lock().await;
+                ok_or_runtime_error(
+                    db_client
+                        .list_indexes_in_extension_collection(&extension_name)
+                        .await,
+                )
+            },
+        );
+        methods.add_async_method(
+            "drop_index_in_collection",
+            async |_, this, (extension_name, index_name): (String, String)| {
+                let db_client = &this.client.lock().await;
+                ok_or_runtime_error(
+                    db_client
+                        .drop_index_in_extension_collection(&extension_name, &index_name)
+                        .await,
+                )
+            },
+        );
+        methods.add_async_method(
+            "watch_collection",
+            async |_, this, (extension_name, since, callback): (String, Option<String>, Function)| {
+                // the lock is released right away, so that the callback can use the client
+                let db_client = this.client.lock().await.clone();
+                let changes = ok_or_runtime_error(
+                    db_client
+                        .watch_extension_collection(
+                            &extension_name,
+                            since.as_deref().unwrap_or("now"),
+                        )
+                        .await,
+                )?;
+                let mut changes = pin!(changes);
+                let mut last_seq = since;
+                while let Some(change) = changes.next().await {
+                    let change = ok_or_runtime_error(change)?;
+                    last_seq = Some(change.seq.clone());
+                    let proceed: Value = callback.call_async(change).await?;
+                    if proceed == Value::Boolean(false) {
+                        break;
+                    }
+                }
+                Ok(last_seq)
+            },
+        );
         methods.add_async_method(
             "create_view_in_collection",
             async |_, this, (extension_name, definition): (String, Document)| {
@@ -111,3 +196,44 @@ impl FromLua for Document {
         Ok(Document::from(lua.from_value(value)?))
     }
 }
+
+/// The count comes first, so extensions reading a single result still get
+/// the number of documents, as before failures were reported.
+impl IntoLuaMulti for BulkResult {
+    fn into_lua_multi(self, lua: &Lua) -> mlua::Result<MultiValue> {
+        let failures: Vec<serde_json::Value> = self
+            .failures
+            .into_iter()
+            .map(|failure| json!({"id": failure.id, "error": failure.error}))
+            .collect();
+        (self.count, lua.to_value(&failures)?).into_lua_multi(lua)
+    }
+}
+
+impl IntoLua for CollectionChange {
+    fn into_lua(self, lua: &Lua) -> mlua::Result<mlua::Value> {
+        let change = lua.create_table()?;
+        change.set("seq", self.seq)?;
+        change.set("id", self.id)?;
+        change.set("deleted", self.deleted)?;
+        change.set("doc", self.document)?;
+        Ok(mlua::Value::Table(change))
+    }
+}
+
+impl IntoLua for ExtensionCollectionIndex {
+    fn into_lua(self, lua: &Lua) -> mlua::Result<mlua::Value> {
+        let fields: Vec<serde_json::Value> = self
+            .fields
+            .into_iter()
+            .map(|(name, direction)| json!({ name: direction }))
+            .collect();
+        lua.to_value(&json!({
+            "name": self.name,
+            "ddoc": self.ddoc,
+            "type": self.index_type,
+            "fields": fields,
+            "partial_filter_selector": self.partial_filter_selector,
+        }))
+    }
+}
@@ -0,0 +1,176 @@
+use anyhow::{bail, Result};
+
+use crate::dictionary_entry::DictionaryEntry;
+use utilities::language::Language;
+
+pub const DEFAULT_FRONT_TEMPLATE: &str = "{word}";
+pub const DEFAULT_BACK_TEMPLATE: &str = "{ipa}<br>{pos}<br>{glosses}{examples}";
+
+const PLACEHOLDERS: [&str; 6] = ["word", "pos", "ipa", "glosses", "examples", "count"];
+
+/// Front and back of the cards with placeholders like `{word}` or `{glosses}`
+pub struct CardTemplates {
+    pub front: String,
+    pub back: String,
+}
+
+impl CardTemplates {
+    pub fn init(front: String, back: String) -> Result<Self> {
+        for template in [&front, &back] {
+            let mut rest = template.as_str();
+            while let Some(start) = rest.find('{') {
+                let Some(end) = rest[start..].find('}') else {
+                    break;
+                };
+                let placeholder = &rest[start + 1..start + end];
+                if !PLACEHOLDERS.contains(&placeholder) {
+                    bail!(
+                        "unknown placeholder '{{{}}}', use one of: {}",
+                        placeholder,
+                        PLACEHOLDERS.map(|p| format!("{{{}}}", p)).join(", ")
+                    );
+                }
+                rest = &rest[start + end..];
+            }
+        }
+        Ok(Self { front, back })
+    }
+}
+
+/// What the placeholders of the card templates are filled with
+pub struct CardFields {
+    word: String,
+    count: u64,
+    pos: Vec<String>,
+    ipa: Vec<String>,
+    glosses: Vec<String>,
+    examples: Vec<String>,
+}
+
+impl CardFields {
+    pub fn of(word: &str, count: u64, entries: &[DictionaryEntry]) -> Self {
+        let mut fields = Self {
+            word: word.to_string(),
+            count,
+            pos: Vec::new(),
+            ipa: Vec::new(),
+            glosses: Vec::new(),
+            examples: Vec::new(),
+        };
+        for entry in entries {
+            push_unique(&mut fields.pos, &entry.pos);
+            for ipa in entry.sounds.iter().filter_map(|sound| sound.ipa.as_ref()) {
+                push_unique(&mut fields.ipa, ipa);
+            }
+            for sense in &entry.senses {
+                if !sense.glosses.is_empty() {
+                    fields.glosses.push(sense.glosses.join(" "));
+                }
+                for text in sense.examples.iter().filter_map(|e| e.text.as_ref()) {
+                    fields.examples.push(text.clone());
+                }
+            }
+        }
+        fields
+    }
+
+    /// Fills the template in one pass, so placeholders in the filled in text
+    /// stay as they are
+    pub fn fill(&self, template: &str) -> String {
+        let mut filled = String::new();
+        let mut rest = template;
+        while let Some(start) = rest.find('{') {
+            let Some(end) = rest[start..].find('}') else {
+                break;
+            };
+            filled.push_str(&rest[..start]);
+            let token = &rest[start..start + end + 1];
+            match self.value_of(&token[1..token.len() - 1]) {
+                Some(value) => filled.push_str(&value),
+                None => filled.push_str(token),
+            }
+            rest = &rest[start + end + 1..];
+        }
+        filled.push_str(rest);
+        filled
+    }
+
+    fn value_of(&self, placeholder: &str) -> Option<String> {
+        let value = match placeholder {
+            "word" => escape(&self.word),
+            "count" => self.count.to_string(),
+            "pos" => escape(&self.pos.join(", ")),
+            "ipa" => escape(&self.ipa.join(", ")),
+            "glosses" => html_list("ol", &self.glosses),
+            "examples" => html_list("ul", &self.examples),
+            _ => return None,
+        };
+        Some(value)
+    }
+}
+
+fn push_unique(values: &mut Vec<String>, value: &str) {
+    if !values.iter().any(|existing| existing == value) {
+        values.push(value.to_string());
+    }
+}
+
+fn html_list(tag: &str, items: &[String]) -> String {
+    if items.is_empty() {
+        return String::new();
+    }
+    let items: String = items
+        .iter()
+        .map(|item| format!("<li>{}</li>", escape(item)))
+        .collect();
+    format!("<{0}>{1}</{0}>", tag, items)
+}
+
+pub fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Tabs and line breaks would end the field or the note
+pub fn field(text: &str) -> String {
+    text.replace('\t', " ").replace('\n', "<br>")
+}
+
+/// Stable across exports and Rust versions, unlike the hashers of std
+pub fn guid(language: Language, word: &str) -> String {
+    const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const FNV_PRIME: u64 = 0x100000001b3;
+    let mut hash = FNV_OFFSET_BASIS;
+    for byte in format!("{}:{}", language, word).bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    format!("wiktionary-{:016x}", hash)
+}
+
+/// Seconds since the epoch at the start of a `YYYY-MM-DD` day in UTC
+pub fn unix_time_of_date(date: &str) -> Result<i64> {
+    let parts: Vec<&str> = date.split('-').collect();
+    let [year, month, day] = parts.as_slice() else {
+        bail!("'{}' isn't a date like 2024-12-31", date);
+    };
+    let (Ok(year), Ok(month), Ok(day)) = (
+        year.parse::<i64>(),
+        month.parse::<i64>(),
+        day.parse::<i64>(),
+    ) else {
+        bail!("'{}' isn't a date like 2024-12-31", date);
+    };
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        bail!("'{}' isn't a date like 2024-12-31", date);
+    }
+    // days from civil, see http://howardhinnant.github.io/date_algorithms.html
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146097 + day_of_era - 719468;
+    Ok(days * 86400)
+}
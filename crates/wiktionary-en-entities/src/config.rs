@@ -1,8 +1,39 @@
+use std::path::PathBuf;
 use utilities::language::Language;
+use utilities::retry::RetryPolicy;
+use utilities::theme::Theme;
 
-#[derive(Default, Clone)]
+/// Number of lookups kept in the cache unless configured otherwise
+pub const DEFAULT_CACHE_SIZE: usize = 1000;
+
+#[derive(Clone)]
 pub struct Config {
     pub language: Language,
+    pub retry_policy: RetryPolicy,
+    /// Maximal number of cached lookups, 0 disables the cache
+    pub cache_size: usize,
+    /// Directory with recordings named like the `audio` of sounds, e.g. a Commons mirror
+    pub audio_dir: Option<PathBuf>,
+    /// Command that plays a recording given as its last argument
+    pub audio_player: Option<String>,
+    /// Line width of the output instead of the width of the terminal
+    pub width: Option<usize>,
+    /// Colors of the output, a preset or a preset with some roles changed
+    pub theme: Theme,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            language: Language::default(),
+            retry_policy: RetryPolicy::default(),
+            cache_size: DEFAULT_CACHE_SIZE,
+            audio_dir: None,
+            audio_player: None,
+            width: None,
+            theme: Theme::default(),
+        }
+    }
 }
 
 impl Config {
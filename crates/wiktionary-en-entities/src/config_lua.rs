@@ -1,8 +1,13 @@
 use mlua::FromLua;
 use mlua::Lua;
+use mlua::Table;
 use mlua::Value;
+use std::path::PathBuf;
+use std::time::Duration;
+use utilities::retry::RetryPolicy;
+use utilities::theme::{Role, Theme};
 
-use crate::config::Config;
+use crate::config::{Config, DEFAULT_CACHE_SIZE};
 
 impl FromLua for Config {
     fn from_lua(value: Value, _lua: &Lua) -> mlua::Result<Self> {
@@ -14,9 +19,66 @@ impl FromLua for Config {
                     language: language_code
                         .parse()
                         .map_err(|err: anyhow::Error| mlua::Error::RuntimeError(err.to_string()))?,
+                    retry_policy: retry_policy_from(table)?,
+                    cache_size: table
+                        .get::<Option<usize>>("cache_size")?
+                        .unwrap_or(DEFAULT_CACHE_SIZE),
+                    audio_dir: table.get::<Option<PathBuf>>("audio_dir")?,
+                    audio_player: table.get("audio_player")?,
+                    width: table
+                        .get::<Option<usize>>("width")?
+                        .filter(|&width| width > 0),
+                    theme: theme_from(table)?,
                 })
             }
             None => Ok(Config::default()),
         }
     }
 }
+
+/// `Config.theme` is the name of a preset, or a table with an optional
+/// `preset` and colors of roles, e.g. `{ preset = "light", headword = "#005f87" }`
+fn theme_from(table: &Table) -> mlua::Result<Theme> {
+    let to_lua_error = |err: anyhow::Error| mlua::Error::RuntimeError(err.to_string());
+    match table.get::<Value>("theme")? {
+        Value::Nil => Ok(Theme::default()),
+        Value::String(preset) => Theme::preset(&preset.to_str()?).map_err(to_lua_error),
+        Value::Table(roles) => {
+            let mut theme = match roles.get::<Option<String>>("preset")? {
+                Some(preset) => Theme::preset(&preset).map_err(to_lua_error)?,
+                None => Theme::default(),
+            };
+            for pair in roles.pairs::<String, String>() {
+                let (key, color) = pair?;
+                if key == "preset" {
+                    continue;
+                }
+                let role: Role = key.parse().map_err(to_lua_error)?;
+                theme.set_color(role, &color).map_err(to_lua_error)?;
+            }
+            Ok(theme)
+        }
+        _ => Err(mlua::Error::RuntimeError(
+            "'theme' must be the name of a preset or a table".to_string(),
+        )),
+    }
+}
+
+fn retry_policy_from(table: &Table) -> mlua::Result<RetryPolicy> {
+    let default = RetryPolicy::default();
+    let seconds = |key: &str, default: Duration| -> mlua::Result<Duration> {
+        match table.get::<Option<f64>>(key)? {
+            Some(seconds) => Duration::try_from_secs_f64(seconds).map_err(|_| {
+                mlua::Error::RuntimeError(format!("'{}' must be a positive number of seconds", key))
+            }),
+            None => Ok(default),
+        }
+    };
+    Ok(RetryPolicy {
+        timeout: seconds("timeout", default.timeout)?,
+        retries: table
+            .get::<Option<u32>>("retries")?
+            .unwrap_or(default.retries),
+        backoff: seconds("retry_backoff", default.backoff)?,
+    })
+}
@@ -5,14 +5,17 @@ use couch_rs::document::TypedCouchDocument;
 use couch_rs::CouchDocument;
 use indoc::formatdoc;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::str::FromStr;
 use textwrap::{fill, indent};
 
 use utilities::anyhow_serde;
-use utilities::colored_string_utils::{horizontal_line, Join, JoinWrap, LINE_WRAP_AT, NEWLINE};
+use utilities::colored_string_utils::{horizontal_line, line_width, Join, JoinWrap, NEWLINE};
 use utilities::language::Language;
+use utilities::theme::{paint, Role};
 
 #[derive(Serialize, Deserialize, Clone, Debug, CouchDocument)]
 pub struct DictionaryEntry {
@@ -30,19 +33,83 @@ pub struct DictionaryEntry {
     pub sounds: Vec<Sound>,
     pub etymology_text: Option<String>,
     #[serde(default)]
+    pub etymology_templates: Vec<EtymologyTemplate>,
+    /// Tells homographs apart, e.g. "bank" the river side and "bank" the institution
+    pub etymology_number: Option<u32>,
+    #[serde(default)]
     pub synonyms: Vec<RelatedWord>,
     #[serde(default)]
     pub antonyms: Vec<RelatedWord>,
+    #[serde(default)]
+    pub hypernyms: Vec<RelatedWord>,
+    #[serde(default)]
+    pub hyponyms: Vec<RelatedWord>,
+    #[serde(default)]
+    pub meronyms: Vec<RelatedWord>,
+    #[serde(default)]
+    pub holonyms: Vec<RelatedWord>,
+    #[serde(default)]
+    pub derived: Vec<RelatedWord>,
+    #[serde(default)]
+    pub related: Vec<RelatedWord>,
+    #[serde(default)]
+    pub coordinate_terms: Vec<RelatedWord>,
+    #[serde(default)]
+    pub forms: Vec<Form>,
+    #[serde(default)]
+    pub categories: Vec<Category>,
+    #[serde(default)]
+    pub descendants: Vec<Descendant>,
+    #[serde(default)]
+    pub hyphenations: Vec<Hyphenation>,
+    /// Fields of the extract that aren't modelled above, kept as they are so
+    /// that they reach CouchDB and lua extensions.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct Sense {
+    pub id: Option<String>,
+    #[serde(default)]
+    pub senseid: Vec<String>,
     #[serde(default)]
     pub glosses: Vec<String>,
+    /// Glosses with their qualifiers, e.g. "(figurative) A person or thing ..."
+    #[serde(default)]
+    pub raw_glosses: Vec<String>,
+    pub qualifier: Option<String>,
     #[serde(default)]
     pub examples: Vec<Example>,
     #[serde(default)]
     pub tags: Vec<String>,
+    #[serde(default)]
+    pub categories: Vec<Category>,
+    #[serde(default)]
+    pub links: Vec<Link>,
+    #[serde(default)]
+    pub wikidata: Vec<String>,
+    #[serde(default)]
+    pub synonyms: Vec<RelatedWord>,
+    #[serde(default)]
+    pub antonyms: Vec<RelatedWord>,
+    #[serde(default)]
+    pub hypernyms: Vec<RelatedWord>,
+    #[serde(default)]
+    pub hyponyms: Vec<RelatedWord>,
+    #[serde(default)]
+    pub meronyms: Vec<RelatedWord>,
+    #[serde(default)]
+    pub holonyms: Vec<RelatedWord>,
+    #[serde(default)]
+    pub derived: Vec<RelatedWord>,
+    #[serde(default)]
+    pub related: Vec<RelatedWord>,
+    #[serde(default)]
+    pub coordinate_terms: Vec<RelatedWord>,
+    /// Unmodelled fields, see [`DictionaryEntry::extra`]
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -51,13 +118,35 @@ pub struct Example {
     #[serde(default)]
     pub reference: Option<String>,
     pub text: Option<String>,
+    /// Unmodelled fields, see [`DictionaryEntry::extra`]
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct Translation {
     pub lang: String,
     pub code: Option<String>,
     pub word: Option<String>,
+    /// Unmodelled fields, see [`DictionaryEntry::extra`]
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// Translations are the same when they have the same word in the same
+/// language, whatever else the extract tells about them
+impl PartialEq for Translation {
+    fn eq(&self, other: &Self) -> bool {
+        (&self.lang, &self.code, &self.word) == (&other.lang, &other.code, &other.word)
+    }
+}
+
+impl Eq for Translation {}
+
+impl Hash for Translation {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (&self.lang, &self.code, &self.word).hash(state);
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -65,8 +154,41 @@ pub struct Sound {
     pub ipa: Option<String>,
     pub enpr: Option<String>,
     pub other: Option<String>,
+    /// File name of a recording on Wikimedia Commons, e.g. "En-us-dictionary.ogg"
+    pub audio: Option<String>,
+    pub ogg_url: Option<String>,
+    pub mp3_url: Option<String>,
     #[serde(default)]
     pub tags: Vec<String>,
+    /// Unmodelled fields, see [`DictionaryEntry::extra`]
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl Sound {
+    /// Accents of a recording in lower case, from its tags and from the region
+    /// in file names like "En-us-dictionary.ogg" as recordings are rarely tagged.
+    pub fn accents(&self) -> Vec<String> {
+        let mut accents: Vec<String> = self.tags.iter().map(|tag| tag.to_lowercase()).collect();
+        let region = self.audio.as_deref().and_then(|audio| {
+            let mut segments = audio.split('-');
+            let region = segments.nth(1)?;
+            let is_region = segments.next().is_some()
+                && (2..=3).contains(&region.len())
+                && region.chars().all(|c| c.is_ascii_alphabetic());
+            is_region.then(|| region.to_lowercase())
+        });
+        if let Some(region) = region {
+            if !accents.contains(&region) {
+                accents.push(region);
+            }
+        }
+        accents
+    }
+
+    pub fn has_accent(&self, accent: &str) -> bool {
+        self.accents().contains(&accent.to_lowercase())
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -75,6 +197,388 @@ pub struct RelatedWord {
     #[serde(default)]
     pub tags: Vec<String>,
     pub sense: Option<String>,
+    /// Unmodelled fields, see [`DictionaryEntry::extra`]
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Category {
+    pub name: String,
+    pub kind: Option<String>,
+    #[serde(default)]
+    pub parents: Vec<String>,
+    pub source: Option<String>,
+    /// Unmodelled fields, see [`DictionaryEntry::extra`]
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// A word of a gloss linked to another page, kept as `[text, target]` like in the extract
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(try_from = "Vec<String>", into = "Vec<String>")]
+pub struct Link {
+    pub text: String,
+    pub target: String,
+}
+
+impl Link {
+    /// The word the link points to without the section, e.g. "meaning" for "meaning#Noun"
+    pub fn target_word(&self) -> &str {
+        self.target
+            .split_once('#')
+            .map_or(self.target.as_str(), |(word, _)| word)
+    }
+}
+
+impl TryFrom<Vec<String>> for Link {
+    type Error = String;
+
+    fn try_from(link: Vec<String>) -> std::result::Result<Self, Self::Error> {
+        match link.as_slice() {
+            [text] => Ok(Link {
+                text: text.clone(),
+                target: text.clone(),
+            }),
+            [text, target, ..] => Ok(Link {
+                text: text.clone(),
+                target: target.clone(),
+            }),
+            [] => Err("a link needs at least a text".to_string()),
+        }
+    }
+}
+
+impl From<Link> for Vec<String> {
+    fn from(link: Link) -> Self {
+        vec![link.text, link.target]
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct EtymologyTemplate {
+    pub name: String,
+    #[serde(default)]
+    pub args: BTreeMap<String, String>,
+    pub expansion: Option<String>,
+    /// Unmodelled fields, see [`DictionaryEntry::extra`]
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AncestorRelation {
+    Inherited,
+    Borrowed,
+    Derived,
+}
+
+impl fmt::Display for AncestorRelation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AncestorRelation::Inherited => write!(f, "inherited from"),
+            AncestorRelation::Borrowed => write!(f, "borrowed from"),
+            AncestorRelation::Derived => write!(f, "derived from"),
+        }
+    }
+}
+
+/// A word an entry descends from, as given by an inh, bor or der template
+#[derive(Clone, Debug, PartialEq)]
+pub struct Ancestor {
+    pub relation: AncestorRelation,
+    pub language_code: String,
+    pub language: String,
+    pub word: Option<String>,
+}
+
+impl EtymologyTemplate {
+    /// The ancestor the template names, `None` for templates such as
+    /// `surf` or `glossary` that don't name one.
+    pub fn ancestor(&self) -> Option<Ancestor> {
+        let relation = match self.name.as_str() {
+            "inh" | "inh+" | "inh-lite" => AncestorRelation::Inherited,
+            "bor" | "bor+" | "lbor" | "slbor" | "obor" | "ubor" | "lbor+" => {
+                AncestorRelation::Borrowed
+            }
+            "der" | "der+" | "uder" => AncestorRelation::Derived,
+            _ => return None,
+        };
+        let language_code = self.args.get("2")?.clone();
+        // "-" and "" stand for an unknown word
+        let word = self
+            .args
+            .get("3")
+            .filter(|word| !word.is_empty() && *word != "-")
+            .cloned();
+        let expansion = self.expansion.as_deref().unwrap_or_default();
+        let language = match &word {
+            Some(word) => expansion.strip_suffix(word.as_str()).unwrap_or(expansion),
+            None => expansion,
+        }
+        .trim();
+        Some(Ancestor {
+            relation,
+            language: if language.is_empty() {
+                language_code.clone()
+            } else {
+                language.to_string()
+            },
+            language_code,
+            word,
+        })
+    }
+}
+
+/// A line of the descendants tree, lines of depth one descend from the entry
+/// and deeper lines from the closest line above them with a lower depth.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Descendant {
+    #[serde(default)]
+    pub depth: u32,
+    /// Shaped like etymology templates, mostly `desc` and `l` templates
+    #[serde(default)]
+    pub templates: Vec<EtymologyTemplate>,
+    #[serde(default)]
+    pub text: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Unmodelled fields, see [`DictionaryEntry::extra`]
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// Arguments of a `desc` template marking the descendant as borrowed
+const BORROWING_ARGS: [&str; 4] = ["bor", "lbor", "slb", "obor"];
+
+impl Descendant {
+    fn descendant_template(&self) -> Option<&EtymologyTemplate> {
+        self.templates
+            .iter()
+            .find(|template| matches!(template.name.as_str(), "desc" | "descendant" | "desctree"))
+    }
+
+    /// The language as written in the text, e.g. "Japanese" of
+    /// "→ Japanese: ディクショナリー (dikushonarī)"
+    pub fn language(&self) -> Option<&str> {
+        let (language, _) = self.text.split_once(':')?;
+        let language = language.trim_start_matches(|c: char| !c.is_alphanumeric());
+        (!language.is_empty()).then_some(language.trim())
+    }
+
+    pub fn language_code(&self) -> Option<&str> {
+        self.descendant_template()
+            .or_else(|| self.templates.first())
+            .and_then(|template| template.args.get("1"))
+            .map(String::as_str)
+    }
+
+    /// Words of the line, a `desc` template lists them from its second argument on
+    pub fn words(&self) -> Vec<&str> {
+        let mut words: Vec<(usize, &str)> = Vec::new();
+        match self.descendant_template() {
+            Some(template) => {
+                for (arg, value) in &template.args {
+                    if let Ok(position) = arg.parse::<usize>() {
+                        if position > 1 {
+                            words.push((position, value));
+                        }
+                    }
+                }
+            }
+            None => {
+                for template in self.templates.iter().filter(|t| t.name == "l") {
+                    if let Some(word) = template.args.get("2") {
+                        words.push((words.len(), word));
+                    }
+                }
+            }
+        }
+        // args are sorted as strings, "10" comes before "2"
+        words.sort_by_key(|(position, _)| *position);
+        words
+            .into_iter()
+            .map(|(_, word)| word)
+            .filter(|word| !word.is_empty() && *word != "-")
+            .collect()
+    }
+
+    pub fn is_borrowing(&self) -> bool {
+        let borrowing_arg = self.descendant_template().is_some_and(|template| {
+            BORROWING_ARGS
+                .iter()
+                .any(|arg| template.args.get(*arg).is_some_and(|value| value == "1"))
+        });
+        borrowing_arg || self.text.trim_start().starts_with('→')
+    }
+}
+
+/// The syllables of a word, e.g. dic‧tion‧a‧ry
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Hyphenation {
+    pub parts: Vec<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Unmodelled fields, see [`DictionaryEntry::extra`]
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl fmt::Display for Hyphenation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.parts.join("‧"))
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Form {
+    pub form: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Unmodelled fields, see [`DictionaryEntry::extra`]
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// Tags of forms that describe an inflection table rather than a form of the word
+pub const NON_FORM_TAGS: [&str; 3] = ["table-tags", "inflection-template", "class"];
+
+impl Form {
+    /// Whether the form is an actual spelling of the word, kaikki also lists
+    /// the templates its inflection tables were generated from as forms.
+    pub fn is_word_form(&self) -> bool {
+        !self
+            .tags
+            .iter()
+            .any(|tag| NON_FORM_TAGS.contains(&tag.as_str()))
+    }
+}
+
+/// Semantic relation between a word and the words listed in one of its fields
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Relation {
+    Synonym,
+    Antonym,
+    Hypernym,
+    Hyponym,
+    Meronym,
+    Holonym,
+    Derived,
+    Related,
+    CoordinateTerm,
+}
+
+impl Relation {
+    pub fn iterator() -> impl Iterator<Item = Relation> {
+        [
+            Relation::Synonym,
+            Relation::Antonym,
+            Relation::Hypernym,
+            Relation::Hyponym,
+            Relation::Meronym,
+            Relation::Holonym,
+            Relation::Derived,
+            Relation::Related,
+            Relation::CoordinateTerm,
+        ]
+        .iter()
+        .copied()
+    }
+
+    /// Name of the field listing the words of the relation
+    pub fn field(&self) -> &'static str {
+        match self {
+            Relation::Synonym => "synonyms",
+            Relation::Antonym => "antonyms",
+            Relation::Hypernym => "hypernyms",
+            Relation::Hyponym => "hyponyms",
+            Relation::Meronym => "meronyms",
+            Relation::Holonym => "holonyms",
+            Relation::Derived => "derived",
+            Relation::Related => "related",
+            Relation::CoordinateTerm => "coordinate_terms",
+        }
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            Relation::Synonym => "Synonyms",
+            Relation::Antonym => "Antonyms",
+            Relation::Hypernym => "Hypernyms",
+            Relation::Hyponym => "Hyponyms",
+            Relation::Meronym => "Meronyms",
+            Relation::Holonym => "Holonyms",
+            Relation::Derived => "Derived terms",
+            Relation::Related => "Related terms",
+            Relation::CoordinateTerm => "Coordinate terms",
+        }
+    }
+}
+
+/// The field listing the words of a relation, entries and senses name them alike
+macro_rules! related_words_of {
+    ($item:expr, $relation:expr) => {
+        match $relation {
+            Relation::Synonym => &$item.synonyms,
+            Relation::Antonym => &$item.antonyms,
+            Relation::Hypernym => &$item.hypernyms,
+            Relation::Hyponym => &$item.hyponyms,
+            Relation::Meronym => &$item.meronyms,
+            Relation::Holonym => &$item.holonyms,
+            Relation::Derived => &$item.derived,
+            Relation::Related => &$item.related,
+            Relation::CoordinateTerm => &$item.coordinate_terms,
+        }
+    };
+}
+
+impl FromStr for Relation {
+    type Err = anyhow::Error;
+
+    fn from_str(relation: &str) -> Result<Self> {
+        match relation {
+            "synonyms" => Ok(Relation::Synonym),
+            "antonyms" => Ok(Relation::Antonym),
+            "hypernyms" => Ok(Relation::Hypernym),
+            "hyponyms" => Ok(Relation::Hyponym),
+            "meronyms" => Ok(Relation::Meronym),
+            "holonyms" => Ok(Relation::Holonym),
+            "derived" => Ok(Relation::Derived),
+            "related" => Ok(Relation::Related),
+            "coordinate_terms" => Ok(Relation::CoordinateTerm),
+            _ => anyhow::bail!(
+                "unknown relation '{}', expected one of: synonyms, antonyms, hypernyms, hyponyms, meronyms, holonyms, derived, related, coordinate_terms",
+                relation
+            ),
+        }
+    }
+}
+
+impl Sense {
+    /// Leading qualifiers of the raw glosses, e.g. "by extension" for
+    /// "(by extension) A reference work on a particular subject".
+    pub fn raw_gloss_qualifiers(&self) -> Vec<&str> {
+        self.raw_glosses
+            .iter()
+            .filter_map(|raw_gloss| raw_gloss.strip_prefix('('))
+            .filter_map(|raw_gloss| raw_gloss.split_once(')'))
+            .map(|(qualifier, _)| qualifier)
+            .collect()
+    }
+
+    /// Words the glosses link to, each only once
+    pub fn cross_references(&self) -> Vec<&str> {
+        let mut seen = HashSet::new();
+        self.links
+            .iter()
+            .map(Link::target_word)
+            .filter(|word| !word.is_empty() && seen.insert(*word))
+            .collect()
+    }
+
+    pub fn related_words(&self, relation: Relation) -> &[RelatedWord] {
+        related_words_of!(self, relation)
+    }
 }
 
 impl FromStr for DictionaryEntry {
@@ -86,10 +590,130 @@ impl FromStr for DictionaryEntry {
 }
 
 impl DictionaryEntry {
+    /// Paths of the fields of the extract that aren't modelled, like
+    /// `head_templates` or `senses.topics` for a field of a sense
+    pub fn unmodelled_fields(&self) -> BTreeSet<String> {
+        let mut fields: BTreeSet<String> = self.extra.keys().cloned().collect();
+        let mut add = |path: &str, extra: &Map<String, Value>| {
+            fields.extend(extra.keys().map(|key| format!("{}.{}", path, key)));
+        };
+        for sense in &self.senses {
+            add("senses", &sense.extra);
+            for example in &sense.examples {
+                add("senses.examples", &example.extra);
+            }
+            for category in &sense.categories {
+                add("senses.categories", &category.extra);
+            }
+            for relation in Relation::iterator() {
+                for related_word in related_words_of!(sense, relation) {
+                    add(&format!("senses.{}", relation.field()), &related_word.extra);
+                }
+            }
+        }
+        for relation in Relation::iterator() {
+            for related_word in related_words_of!(self, relation) {
+                add(relation.field(), &related_word.extra);
+            }
+        }
+        for translation in &self.translations {
+            add("translations", &translation.extra);
+        }
+        for sound in &self.sounds {
+            add("sounds", &sound.extra);
+        }
+        for template in &self.etymology_templates {
+            add("etymology_templates", &template.extra);
+        }
+        for form in &self.forms {
+            add("forms", &form.extra);
+        }
+        for category in &self.categories {
+            add("categories", &category.extra);
+        }
+        for descendant in &self.descendants {
+            add("descendants", &descendant.extra);
+            for template in &descendant.templates {
+                add("descendants.templates", &template.extra);
+            }
+        }
+        for hyphenation in &self.hyphenations {
+            add("hyphenations", &hyphenation.extra);
+        }
+        fields
+    }
+
+    /// Ancestors named by the etymology templates, nearest first
+    pub fn ancestry(&self) -> Vec<Ancestor> {
+        let mut ancestry: Vec<Ancestor> = Vec::new();
+        for ancestor in self
+            .etymology_templates
+            .iter()
+            .filter_map(EtymologyTemplate::ancestor)
+        {
+            if !ancestry.contains(&ancestor) {
+                ancestry.push(ancestor);
+            }
+        }
+        ancestry
+    }
+
+    /// Words of the relation listed for the entry and for any of its senses,
+    /// each word only once and never the word of the entry itself.
+    pub fn related_words(&self, relation: Relation) -> Vec<&RelatedWord> {
+        let entry_level = related_words_of!(self, relation);
+        let sense_level = self
+            .senses
+            .iter()
+            .flat_map(|sense| sense.related_words(relation));
+        let mut seen = HashSet::new();
+        entry_level
+            .iter()
+            .chain(sense_level)
+            .filter(|related_word| related_word.word != self.word)
+            .filter(|related_word| seen.insert(related_word.word.as_str()))
+            .collect()
+    }
+
+    /// Names of the categories of the entry and of its senses, sorted and
+    /// each name only once.
+    pub fn category_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self
+            .categories
+            .iter()
+            .chain(self.senses.iter().flat_map(|sense| &sense.categories))
+            .map(|category| category.name.as_str())
+            .collect();
+        names.sort_unstable();
+        names.dedup();
+        names
+    }
+
+    /// Syllables of the first hyphenation, empty when none is known
+    pub fn syllables(&self) -> &[String] {
+        self.hyphenations
+            .first()
+            .map(|hyphenation| hyphenation.parts.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Whether any hyphenation of the word has the number of syllables, words
+    /// like "dictionary" are split in more than one way.
+    pub fn has_syllable_count(&self, count: usize) -> bool {
+        self.hyphenations
+            .iter()
+            .any(|hyphenation| hyphenation.parts.len() == count)
+    }
+
+    pub fn supported_translations(&self) -> Vec<&Translation> {
+        supported_translations(&self.translations)
+    }
+
     pub fn to_pretty_string(&self) -> String {
         let mut entries: Vec<ColoredString> = Vec::new();
         let etymology = format_etymology(&self.etymology_text);
         let sounds = format_sounds(&self.sounds);
+        let forms = format_forms(&self.forms);
         let senses = format_senses(&self.senses);
         let translations = format_translations(&self.translations);
 
@@ -99,6 +723,9 @@ impl DictionaryEntry {
         if let Some(sounds) = sounds {
             entries.push(sounds);
         }
+        if let Some(forms) = forms {
+            entries.push(forms);
+        }
         if let Some(senses) = senses {
             entries.push(senses);
         }
@@ -110,13 +737,14 @@ impl DictionaryEntry {
         formatdoc!(
             "
             {}
-            {} ({})
+            {} ({}){}
             {}
             {}
             ",
             horizontal_line,
-            self.word.green().bold(),
-            self.pos,
+            paint(&self.word, Role::Headword).bold(),
+            paint(&self.pos, Role::Pos),
+            format_hyphenations(&self.hyphenations),
             horizontal_line,
             format!("{}{}{}", NEWLINE, horizontal_line, NEWLINE)
                 .normal()
@@ -131,19 +759,24 @@ impl fmt::Display for DictionaryEntry {
     }
 }
 
+fn format_section_title(title: &str) -> ColoredString {
+    paint(title, Role::SectionTitle).bold()
+}
+
 fn format_etymology(etymology: &Option<String>) -> Option<ColoredString> {
     if let Some(etymology) = etymology {
-        let res = vec!["Etymology:".bold(), etymology.normal()];
-        return Some(NEWLINE.normal().joinwrap(res, LINE_WRAP_AT));
+        let res = vec![format_section_title("Etymology:"), etymology.normal()];
+        return Some(NEWLINE.normal().joinwrap(res, line_width()));
     }
     None
 }
 
 fn senses_to_strings(senses: &[Sense]) -> Vec<ColoredString> {
+    // senses are numbered from one as they are addressed, e.g. `dictionary#2`
     senses
         .iter()
         .enumerate()
-        .map(|(i, sense)| format_sense(sense, i))
+        .map(|(i, sense)| format_sense(sense, i + 1))
         .collect()
 }
 
@@ -156,25 +789,41 @@ fn format_senses(senses: &[Sense]) -> Option<ColoredString> {
 
 fn format_sense(sense: &Sense, index: usize) -> ColoredString {
     let mut res: Vec<ColoredString> = Vec::new();
+    let qualifiers = sense.raw_gloss_qualifiers();
     let title = format!(
         "{}. {}",
         index.to_string().bold(),
-        format_tags(&sense.tags).bold()
+        match qualifiers.as_slice() {
+            [] => format_tags(&sense.tags),
+            qualifiers => format!("({})", qualifiers.join("; ")),
+        }
+        .bold()
     )
     .normal();
     res.push(title);
-    res.push(fill(&format_glosses(&sense.glosses), LINE_WRAP_AT).normal());
+    res.push(fill(&format_glosses(&sense.glosses), line_width()).normal());
     if !sense.examples.is_empty() {
         res.push(format_examples(&sense.examples));
     }
+    if let Some(cross_references) = format_cross_references(&sense.cross_references()) {
+        res.push(cross_references);
+    }
     NEWLINE.normal().join(res)
 }
 
+fn format_cross_references(words: &[&str]) -> Option<ColoredString> {
+    if words.is_empty() {
+        return None;
+    }
+    Some(fill(&format!("See: {}", words.join(", ")), line_width()).dimmed())
+}
+
 fn format_examples(examples: &[Example]) -> ColoredString {
     indent(
-        &NEWLINE
-            .normal()
-            .joinwrap(examples_to_strings(examples), LINE_WRAP_AT - 1),
+        &NEWLINE.normal().joinwrap(
+            examples_to_strings(examples),
+            line_width().saturating_sub(1),
+        ),
         " ",
     )
     .normal()
@@ -185,7 +834,10 @@ fn format_sounds(sounds: &[Sound]) -> Option<ColoredString> {
     if as_strings.is_empty() {
         return None;
     }
-    let res = vec!["Pronunciation".bold(), NEWLINE.normal().join(as_strings)];
+    let res = vec![
+        format_section_title("Pronunciation"),
+        NEWLINE.normal().join(as_strings),
+    ];
     Some(NEWLINE.normal().join(res))
 }
 
@@ -218,6 +870,30 @@ fn sounds_to_strings(sounds: &[Sound]) -> Vec<ColoredString> {
     results
 }
 
+fn format_forms(forms: &[Form]) -> Option<ColoredString> {
+    let as_strings: Vec<ColoredString> = forms
+        .iter()
+        .filter(|form| form.is_word_form())
+        .map(|form| format!(" {} {}", form.form, format_tags(&form.tags).italic()).normal())
+        .collect();
+    if as_strings.is_empty() {
+        return None;
+    }
+    let res = vec![
+        format_section_title("Forms"),
+        NEWLINE.normal().join(as_strings),
+    ];
+    Some(NEWLINE.normal().join(res))
+}
+
+fn format_hyphenations(hyphenations: &[Hyphenation]) -> ColoredString {
+    if hyphenations.is_empty() {
+        return "".normal();
+    }
+    let as_strings: Vec<String> = hyphenations.iter().map(Hyphenation::to_string).collect();
+    format!(" {}", as_strings.join(", ")).dimmed()
+}
+
 fn format_tags(tags: &[String]) -> String {
     match tags {
         [] => String::new(),
@@ -238,7 +914,7 @@ fn examples_to_strings(examples: &[Example]) -> Vec<ColoredString> {
         .map(|(i, example)| {
             format!(
                 "{}. {}",
-                i.to_string().italic(),
+                paint(&i.to_string(), Role::ExampleIndex).italic(),
                 example.text.clone().unwrap_or(String::new())
             )
             .normal()
@@ -246,7 +922,8 @@ fn examples_to_strings(examples: &[Example]) -> Vec<ColoredString> {
         .collect()
 }
 
-fn translations_to_strings(translations: &[Translation]) -> Vec<ColoredString> {
+/// Translations into languages with a dictionary, sorted by language and without duplicates
+fn supported_translations(translations: &[Translation]) -> Vec<&Translation> {
     let langs: Vec<Option<String>> = Language::iterator()
         .map(|lang| Some(lang.to_string()))
         .collect();
@@ -257,6 +934,10 @@ fn translations_to_strings(translations: &[Translation]) -> Vec<ColoredString> {
     let mut filtered_translations = Vec::from_iter(translations_as_set);
     filtered_translations.sort_by(|t1, t2| t1.lang.cmp(&t2.lang));
     filtered_translations
+}
+
+fn translations_to_strings(translations: &[Translation]) -> Vec<ColoredString> {
+    supported_translations(translations)
         .iter()
         .map(|translation| {
             format!(
@@ -274,7 +955,7 @@ fn format_translations(translations: &[Translation]) -> Option<ColoredString> {
         return None;
     }
     let res = vec![
-        "Translations".bold(),
+        format_section_title("Translations"),
         NEWLINE.normal().join(translations_to_strings(translations)),
     ];
     Some(NEWLINE.normal().join(res))
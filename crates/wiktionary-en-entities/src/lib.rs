@@ -1,6 +1,8 @@
+pub mod anki;
 pub mod config;
 pub mod config_lua;
 pub mod dictionary_entry;
-pub mod dictionary_entry_lua;
+pub mod lua_bridge;
+pub mod render;
 pub mod result;
-pub mod result_lua;
+pub mod validation;
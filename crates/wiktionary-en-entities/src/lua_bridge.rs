@@ -0,0 +1,258 @@
+use colored::Colorize;
+use mlua::serde::SerializeOptions;
+use mlua::{FromLua, IntoLua, Lua, LuaSerdeExt, Table, Value};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::sync::Mutex;
+
+use crate::dictionary_entry::{DictionaryEntry, Form};
+use crate::result::{DictionaryResult, DidYouMean};
+
+/// Fields lua scripts used to see under another name, old name first
+const RENAMED_FIELDS: [(&str, &str); 1] = [("etymology", "etymology_text")];
+
+/// Field the unmodelled fields were nested in before they became fields of the entry
+const EXTRA_FIELD: &str = "extra";
+
+static WARNED_FIELDS: Mutex<Option<HashSet<&'static str>>> = Mutex::new(None);
+
+/// Entities reach lua exactly as serde sees them, `None` becomes `nil` so
+/// that missing values can be tested for in lua.
+fn to_lua<T: Serialize>(lua: &Lua, value: &T) -> mlua::Result<Value> {
+    let options = SerializeOptions::new()
+        .serialize_none_to_null(false)
+        .serialize_unit_to_null(false);
+    lua.to_value_with(value, options)
+}
+
+fn from_lua<T: DeserializeOwned>(lua: &Lua, value: Value) -> mlua::Result<T> {
+    lua.from_value(value)
+}
+
+fn field_name(field: &Value) -> Option<String> {
+    Some(field.as_string()?.to_str().ok()?.to_string())
+}
+
+fn renamed_to(field: &Value) -> Option<(&'static str, &'static str)> {
+    let field = field_name(field)?;
+    RENAMED_FIELDS
+        .iter()
+        .find(|(old_name, _)| *old_name == field)
+        .copied()
+}
+
+/// Warns once per field, a formatter reads the same field for every entry
+fn warn_once(field: &'static str, message: impl FnOnce() -> String) {
+    let mut warned_fields = WARNED_FIELDS.lock().unwrap_or_else(|err| err.into_inner());
+    if warned_fields.get_or_insert_default().insert(field) {
+        eprintln!("{} {}", "warning:".yellow(), message());
+    }
+}
+
+fn warn_renamed((old_name, new_name): (&'static str, &'static str)) {
+    warn_once(old_name, || {
+        format!(
+            "the field '{}' of dictionary entries is called '{}' now",
+            old_name, new_name
+        )
+    });
+}
+
+/// Redirects reads and writes of renamed fields to their new name, and reads
+/// of `extra` to a table of the unmodelled fields, which are fields of the
+/// entry itself now.
+fn redirect_old_fields(lua: &Lua, entry: &Table, extra_fields: Vec<String>) -> mlua::Result<()> {
+    let metatable = lua.create_table()?;
+    metatable.set(
+        "__index",
+        lua.create_function(move |lua, (entry, field): (Table, Value)| {
+            if let Some(renamed) = renamed_to(&field) {
+                warn_renamed(renamed);
+                return entry.raw_get::<Value>(renamed.1);
+            }
+            if field_name(&field).as_deref() != Some(EXTRA_FIELD) {
+                return Ok(Value::Nil);
+            }
+            warn_once(EXTRA_FIELD, || {
+                "the fields of 'extra' are fields of dictionary entries now".to_string()
+            });
+            let extra = lua.create_table()?;
+            for name in &extra_fields {
+                extra.raw_set(name.as_str(), entry.raw_get::<Value>(name.as_str())?)?;
+            }
+            // kept on the entry, changes to it are moved back by `rename_old_fields`
+            entry.raw_set(EXTRA_FIELD, &extra)?;
+            Ok(Value::Table(extra))
+        })?,
+    )?;
+    metatable.set(
+        "__newindex",
+        lua.create_function(|_, (entry, field, value): (Table, Value, Value)| {
+            match renamed_to(&field) {
+                Some(renamed) => {
+                    warn_renamed(renamed);
+                    entry.raw_set(renamed.1, value)
+                }
+                None => entry.raw_set(field, value),
+            }
+        })?,
+    )?;
+    entry.set_metatable(Some(metatable))?;
+    redirect_link_fields(lua, entry)
+}
+
+/// Links reach lua as `[text, target]` pairs, their former fields `text`,
+/// `target` and `target_word` are still read from and written to the pair.
+fn redirect_link_fields(lua: &Lua, entry: &Table) -> mlua::Result<()> {
+    let Value::Table(senses) = entry.raw_get::<Value>("senses")? else {
+        return Ok(());
+    };
+    let metatable = lua.create_table()?;
+    metatable.set(
+        "__index",
+        lua.create_function(|lua, (link, field): (Table, Value)| {
+            let Some(field) = field_name(&field) else {
+                return Ok(Value::Nil);
+            };
+            warn_link_fields();
+            let target = || -> mlua::Result<Option<String>> { link.raw_get(2) };
+            match field.as_str() {
+                "text" => link.raw_get(1),
+                "target" => target()?.into_lua(lua),
+                "target_word" => target()?
+                    .map(|target| {
+                        target
+                            .split_once('#')
+                            .map_or(target.clone(), |(word, _)| word.to_string())
+                    })
+                    .into_lua(lua),
+                _ => Ok(Value::Nil),
+            }
+        })?,
+    )?;
+    metatable.set(
+        "__newindex",
+        lua.create_function(|_, (link, field, value): (Table, Value, Value)| {
+            match field_name(&field).as_deref() {
+                Some("text") => {
+                    warn_link_fields();
+                    link.raw_set(1, value)
+                }
+                Some("target") => {
+                    warn_link_fields();
+                    link.raw_set(2, value)
+                }
+                _ => link.raw_set(field, value),
+            }
+        })?,
+    )?;
+    for sense in senses.sequence_values::<Table>() {
+        let Value::Table(links) = sense?.raw_get::<Value>("links")? else {
+            continue;
+        };
+        for link in links.sequence_values::<Table>() {
+            link?.set_metatable(Some(metatable.clone()))?;
+        }
+    }
+    Ok(())
+}
+
+fn warn_link_fields() {
+    warn_once("links", || {
+        "links are [text, target] pairs now, read 'text' and 'target' as link[1] and link[2]"
+            .to_string()
+    });
+}
+
+/// Moves fields a lua script set under their old name to the new one, and
+/// the fields of `extra` back onto the entry
+fn rename_old_fields(entry: &Table) -> mlua::Result<()> {
+    for renamed @ (old_name, new_name) in RENAMED_FIELDS {
+        let value: Value = entry.raw_get(old_name)?;
+        if value.is_nil() {
+            continue;
+        }
+        warn_renamed(renamed);
+        if entry.raw_get::<Value>(new_name)?.is_nil() {
+            entry.raw_set(new_name, value)?;
+        }
+        entry.raw_set(old_name, Value::Nil)?;
+    }
+    if let Value::Table(extra) = entry.raw_get::<Value>(EXTRA_FIELD)? {
+        for field in extra.pairs::<Value, Value>() {
+            let (name, value) = field?;
+            entry.raw_set(name, value)?;
+        }
+        entry.raw_set(EXTRA_FIELD, Value::Nil)?;
+    }
+    Ok(())
+}
+
+impl IntoLua for DictionaryEntry {
+    fn into_lua(self, lua: &Lua) -> mlua::Result<Value> {
+        let extra_fields = self.extra.keys().cloned().collect();
+        let entry = to_lua(lua, &self)?;
+        if let Some(table) = entry.as_table() {
+            redirect_old_fields(lua, table, extra_fields)?;
+        }
+        Ok(entry)
+    }
+}
+
+impl FromLua for DictionaryEntry {
+    fn from_lua(value: Value, lua: &Lua) -> mlua::Result<Self> {
+        if let Some(table) = value.as_table() {
+            rename_old_fields(table)?;
+        }
+        from_lua(lua, value)
+    }
+}
+
+impl FromLua for Form {
+    fn from_lua(value: Value, lua: &Lua) -> mlua::Result<Self> {
+        from_lua(lua, value)
+    }
+}
+
+impl IntoLua for DictionaryResult {
+    fn into_lua(self, lua: &Lua) -> mlua::Result<Value> {
+        let result = to_lua(lua, &self)?;
+        if let Some(table) = result.as_table() {
+            let hits = table.get::<Table>("hits")?;
+            for (hit, entry) in hits.sequence_values::<Table>().zip(&self.hits) {
+                redirect_old_fields(lua, &hit?, entry.extra.keys().cloned().collect())?;
+            }
+        }
+        Ok(result)
+    }
+}
+
+impl FromLua for DictionaryResult {
+    fn from_lua(value: Value, lua: &Lua) -> mlua::Result<Self> {
+        if let Some(hits) = value
+            .as_table()
+            .map(|table| table.get::<Value>("hits"))
+            .transpose()?
+            .as_ref()
+            .and_then(Value::as_table)
+        {
+            for hit in hits.sequence_values::<Table>() {
+                rename_old_fields(&hit?)?;
+            }
+        }
+        from_lua(lua, value)
+    }
+}
+
+impl IntoLua for DidYouMean {
+    fn into_lua(self, lua: &Lua) -> mlua::Result<Value> {
+        to_lua(lua, &self)
+    }
+}
+
+impl FromLua for DidYouMean {
+    fn from_lua(value: Value, lua: &Lua) -> mlua::Result<Self> {
+        from_lua(lua, value)
+    }
+}
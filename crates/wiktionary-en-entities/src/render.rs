@@ -0,0 +1,366 @@
+use anyhow::{bail, Result};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::dictionary_entry::{DictionaryEntry, Relation, Sense, Sound};
+use crate::result::DictionaryResult;
+
+const WIKTIONARY_URL: &str = "https://en.wiktionary.org/wiki/";
+
+/// Formats entries are rendered to for pasting them into wikis and documents
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RenderFormat {
+    /// GitHub flavoured Markdown
+    Markdown,
+    /// A standalone HTML document
+    Html,
+}
+
+impl FromStr for RenderFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(format: &str) -> Result<Self> {
+        match format.to_lowercase().as_str() {
+            "markdown" | "md" => Ok(RenderFormat::Markdown),
+            "html" => Ok(RenderFormat::Html),
+            _ => bail!("unknown format '{}', use 'markdown' or 'html'", format),
+        }
+    }
+}
+
+impl fmt::Display for RenderFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RenderFormat::Markdown => write!(f, "markdown"),
+            RenderFormat::Html => write!(f, "html"),
+        }
+    }
+}
+
+/// The markup of a format, text passed to it is escaped by the caller
+trait Markup {
+    fn escape(&self, text: &str) -> String;
+    fn heading(&self, level: usize, text: &str) -> String;
+    fn paragraph(&self, text: &str) -> String;
+    fn list(&self, items: &[String], ordered: bool) -> String;
+    fn emphasis(&self, text: &str) -> String;
+    fn link(&self, text: &str, url: &str) -> String;
+    fn document(&self, title: &str, blocks: &[String]) -> String;
+}
+
+struct Markdown;
+
+impl Markup for Markdown {
+    fn escape(&self, text: &str) -> String {
+        let mut escaped = String::with_capacity(text.len());
+        for c in text.chars() {
+            if matches!(
+                c,
+                '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '#' | '|'
+            ) {
+                escaped.push('\\');
+            }
+            escaped.push(c);
+        }
+        escaped
+    }
+
+    fn heading(&self, level: usize, text: &str) -> String {
+        format!("{} {}", "#".repeat(level), text)
+    }
+
+    /// Lines of a paragraph would be joined, each becomes a paragraph
+    fn paragraph(&self, text: &str) -> String {
+        text.lines()
+            .filter(|line| !line.trim().is_empty())
+            .collect::<Vec<&str>>()
+            .join("\n\n")
+    }
+
+    /// Lines after the first of an item are indented to stay in the item
+    fn list(&self, items: &[String], ordered: bool) -> String {
+        items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| {
+                let marker = if ordered {
+                    format!("{}. ", i + 1)
+                } else {
+                    "- ".to_string()
+                };
+                let indentation = " ".repeat(marker.len());
+                let mut lines = item.lines();
+                let mut rendered = format!("{}{}", marker, lines.next().unwrap_or_default());
+                for line in lines {
+                    rendered.push('\n');
+                    if !line.is_empty() {
+                        rendered.push_str(&indentation);
+                        rendered.push_str(line);
+                    }
+                }
+                rendered
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    fn emphasis(&self, text: &str) -> String {
+        format!("*{}*", text)
+    }
+
+    fn link(&self, text: &str, url: &str) -> String {
+        format!("[{}]({})", text, url)
+    }
+
+    fn document(&self, _title: &str, blocks: &[String]) -> String {
+        format!("{}\n", blocks.join("\n\n"))
+    }
+}
+
+struct Html;
+
+impl Markup for Html {
+    fn escape(&self, text: &str) -> String {
+        text.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+    }
+
+    fn heading(&self, level: usize, text: &str) -> String {
+        format!("<h{0}>{1}</h{0}>", level, text)
+    }
+
+    fn paragraph(&self, text: &str) -> String {
+        format!(
+            "<p>{}</p>",
+            text.lines().collect::<Vec<&str>>().join("<br>\n")
+        )
+    }
+
+    fn list(&self, items: &[String], ordered: bool) -> String {
+        let tag = if ordered { "ol" } else { "ul" };
+        let items: Vec<String> = items
+            .iter()
+            .map(|item| format!("<li>{}</li>", item))
+            .collect();
+        format!("<{0}>\n{1}\n</{0}>", tag, items.join("\n"))
+    }
+
+    fn emphasis(&self, text: &str) -> String {
+        format!("<em>{}</em>", text)
+    }
+
+    fn link(&self, text: &str, url: &str) -> String {
+        format!("<a href=\"{}\">{}</a>", self.escape(url), text)
+    }
+
+    fn document(&self, title: &str, blocks: &[String]) -> String {
+        format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n</head>\n<body>\n{}\n</body>\n</html>\n",
+            title,
+            blocks.join("\n")
+        )
+    }
+}
+
+fn markup_of(format: RenderFormat) -> &'static dyn Markup {
+    match format {
+        RenderFormat::Markdown => &Markdown,
+        RenderFormat::Html => &Html,
+    }
+}
+
+impl DictionaryEntry {
+    pub fn render(&self, format: RenderFormat) -> String {
+        let markup = markup_of(format);
+        markup.document(&markup.escape(&self.word), &entry_blocks(markup, self))
+    }
+}
+
+impl DictionaryResult {
+    /// Renders all hits into one document, preceded by the banners
+    pub fn render(&self, format: RenderFormat) -> String {
+        let markup = markup_of(format);
+        let mut blocks = Vec::new();
+        for form_of in &self.form_of {
+            blocks.push(markup.paragraph(&markup.escape(&format!(
+                "{} → {} of {}",
+                form_of.form,
+                form_of.relation(),
+                form_of.lemma
+            ))));
+        }
+        if let Some(did_you_mean) = &self.did_you_mean {
+            blocks.push(markup.paragraph(&format!(
+                "No result for {}. Did you mean {}?",
+                markup.escape(&did_you_mean.searched_for),
+                markup.emphasis(&markup.escape(&did_you_mean.suggestion))
+            )));
+        }
+        for hit in &self.hits {
+            blocks.extend(entry_blocks(markup, hit));
+        }
+        match self.remaining_hits {
+            0 => {}
+            1 => blocks.push(markup.paragraph("1 more result")),
+            n => blocks.push(markup.paragraph(&format!("{} more results", n))),
+        }
+        markup.document(&markup.escape(&self.word), &blocks)
+    }
+}
+
+fn entry_blocks(markup: &dyn Markup, entry: &DictionaryEntry) -> Vec<String> {
+    let mut blocks = vec![markup.heading(
+        2,
+        &markup.escape(&format!("{} ({})", entry.word, entry.pos)),
+    )];
+    if !entry.hyphenations.is_empty() {
+        let hyphenations: Vec<String> = entry.hyphenations.iter().map(|h| h.to_string()).collect();
+        blocks.push(markup.paragraph(&markup.emphasis(&markup.escape(&hyphenations.join(", ")))));
+    }
+    if let Some(etymology) = &entry.etymology_text {
+        blocks.push(markup.heading(3, "Etymology"));
+        blocks.push(markup.paragraph(&markup.escape(etymology)));
+    }
+    let sounds: Vec<String> = entry
+        .sounds
+        .iter()
+        .flat_map(|sound| sound_items(markup, sound))
+        .collect();
+    if !sounds.is_empty() {
+        blocks.push(markup.heading(3, "Pronunciation"));
+        blocks.push(markup.list(&sounds, false));
+    }
+    let forms: Vec<String> = entry
+        .forms
+        .iter()
+        .filter(|form| form.is_word_form())
+        .map(|form| with_tags(markup, &markup.escape(&form.form), &form.tags))
+        .collect();
+    if !forms.is_empty() {
+        blocks.push(markup.heading(3, "Forms"));
+        blocks.push(markup.list(&forms, false));
+    }
+    if !entry.senses.is_empty() {
+        let senses: Vec<String> = entry
+            .senses
+            .iter()
+            .map(|sense| sense_item(markup, sense))
+            .collect();
+        blocks.push(markup.heading(3, "Senses"));
+        blocks.push(markup.list(&senses, true));
+    }
+    for relation in Relation::iterator() {
+        let words: Vec<String> = entry
+            .related_words(relation)
+            .iter()
+            .map(|related_word| word_link(markup, &related_word.word))
+            .collect();
+        if !words.is_empty() {
+            blocks.push(markup.heading(3, relation.title()));
+            blocks.push(markup.paragraph(&words.join(", ")));
+        }
+    }
+    let translations: Vec<String> = entry
+        .supported_translations()
+        .iter()
+        .map(|translation| {
+            format!(
+                "{}: {}",
+                markup.emphasis(&markup.escape(&translation.lang)),
+                markup.escape(translation.word.as_deref().unwrap_or_default())
+            )
+        })
+        .collect();
+    if !translations.is_empty() {
+        blocks.push(markup.heading(3, "Translations"));
+        blocks.push(markup.list(&translations, false));
+    }
+    blocks
+}
+
+fn sound_items(markup: &dyn Markup, sound: &Sound) -> Vec<String> {
+    [
+        ("IPA", &sound.ipa),
+        ("enPr", &sound.enpr),
+        ("other", &sound.other),
+    ]
+    .into_iter()
+    .filter_map(|(notation, pronunciation)| {
+        let pronunciation = pronunciation.as_ref()?;
+        Some(with_tags(
+            markup,
+            &format!("{}: {}", notation, markup.escape(pronunciation)),
+            &sound.tags,
+        ))
+    })
+    .collect()
+}
+
+fn sense_item(markup: &dyn Markup, sense: &Sense) -> String {
+    let qualifiers = sense.raw_gloss_qualifiers();
+    let qualifiers = match qualifiers.as_slice() {
+        [] => sense.tags.join(", "),
+        qualifiers => qualifiers.join("; "),
+    };
+    let mut item = sense
+        .glosses
+        .iter()
+        .map(|gloss| markup.escape(gloss))
+        .collect::<Vec<String>>()
+        .join(" ");
+    if !qualifiers.is_empty() {
+        item = format!(
+            "{} {}",
+            markup.emphasis(&markup.escape(&format!("({})", qualifiers))),
+            item
+        );
+    }
+    let mut details: Vec<String> = sense
+        .examples
+        .iter()
+        .filter_map(|example| example.text.as_ref())
+        .map(|text| markup.emphasis(&markup.escape(text)))
+        .collect();
+    let cross_references = sense.cross_references();
+    if !cross_references.is_empty() {
+        let links: Vec<String> = cross_references
+            .iter()
+            .map(|word| word_link(markup, word))
+            .collect();
+        details.push(format!("See: {}", links.join(", ")));
+    }
+    if !details.is_empty() {
+        item = format!("{}\n{}", item, markup.list(&details, false));
+    }
+    item
+}
+
+fn with_tags(markup: &dyn Markup, text: &str, tags: &[String]) -> String {
+    if tags.is_empty() {
+        return text.to_string();
+    }
+    format!(
+        "{} {}",
+        text,
+        markup.emphasis(&markup.escape(&format!("({})", tags.join(", "))))
+    )
+}
+
+fn word_link(markup: &dyn Markup, word: &str) -> String {
+    markup.link(&markup.escape(word), &wiktionary_url(word))
+}
+
+/// Page of the word on Wiktionary, spaces become underscores like in page titles
+fn wiktionary_url(word: &str) -> String {
+    let mut url = WIKTIONARY_URL.to_string();
+    for byte in word.replace(' ', "_").bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'_' | b'-' | b'.' | b'~') {
+            url.push(byte as char);
+        } else {
+            url.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    url
+}
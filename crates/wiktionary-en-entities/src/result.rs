@@ -1,9 +1,11 @@
 use crate::dictionary_entry::DictionaryEntry;
 use colored::Colorize;
 use indoc::formatdoc;
+use serde::{Deserialize, Serialize};
 use std::fmt;
+use utilities::theme::{paint, Role};
 
-#[derive(Clone)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct DidYouMean {
     pub searched_for: String,
     pub suggestion: String,
@@ -19,15 +21,100 @@ impl fmt::Display for DidYouMean {
     }
 }
 
-#[derive(Clone)]
+/// A searched form that was resolved to the entries of its lemma
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct FormOf {
+    pub form: String,
+    pub tags: Vec<String>,
+    pub lemma: String,
+}
+
+impl FormOf {
+    /// How the form relates to the lemma, e.g. "plural"
+    pub fn relation(&self) -> String {
+        match self.tags.as_slice() {
+            [] => "form".to_string(),
+            tags => tags.join(" "),
+        }
+    }
+}
+
+impl fmt::Display for FormOf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} → {} of {}",
+            paint(&self.form, Role::Form),
+            self.relation(),
+            paint(&self.lemma, Role::Headword).bold()
+        )
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
 pub struct DictionaryResult {
     pub word: String,
     pub did_you_mean: Option<DidYouMean>,
     pub hits: Vec<DictionaryEntry>,
+    #[serde(default)]
+    pub remaining_hits: usize,
+    #[serde(default)]
+    pub form_of: Vec<FormOf>,
+    /// Continues the hits with `--bookmark`, set while more hits may follow
+    #[serde(default)]
+    pub bookmark: Option<String>,
+}
+
+impl DictionaryResult {
+    /// Narrows every hit to the sense with the number, counted from one,
+    /// hits with fewer senses are left out.
+    pub fn with_sense(mut self, number: usize) -> Self {
+        self.hits = self
+            .hits
+            .into_iter()
+            .filter_map(|mut hit| {
+                let sense = hit.senses.get(number.checked_sub(1)?)?.clone();
+                hit.senses = vec![sense];
+                Some(hit)
+            })
+            .collect();
+        self
+    }
+
+    /// Keeps the hits with a hyphenation of the number of syllables
+    pub fn with_syllable_count(mut self, count: usize) -> Self {
+        self.hits.retain(|hit| hit.has_syllable_count(count));
+        self
+    }
+
+    pub fn more_results_banner(&self) -> Option<String> {
+        let more_results = match self.remaining_hits {
+            0 => None,
+            1 => Some(format!("{} more result", paint("1", Role::Number))),
+            n => Some(format!(
+                "{} more results",
+                paint(&n.to_string(), Role::Number)
+            )),
+        };
+        match (more_results, &self.bookmark) {
+            (Some(more_results), Some(bookmark)) => Some(format!(
+                "{}, continue with --bookmark {}",
+                more_results, bookmark
+            )),
+            (None, Some(bookmark)) => Some(format!(
+                "More results may follow, continue with --bookmark {}",
+                bookmark
+            )),
+            (more_results, None) => more_results,
+        }
+    }
 }
 
 impl fmt::Display for DictionaryResult {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for form_of in &self.form_of {
+            writeln!(f, "{}", form_of)?;
+        }
         if let Some(did_you_mean) = &self.did_you_mean {
             writeln!(
                 f,
@@ -38,6 +125,9 @@ impl fmt::Display for DictionaryResult {
         for hit in &self.hits {
             writeln!(f, "{}", hit)?;
         }
+        if let Some(more_results) = self.more_results_banner() {
+            writeln!(f, "{}", more_results)?;
+        }
         Ok(())
     }
 }
@@ -48,7 +138,7 @@ fn did_you_mean_banner(search_term: &str, partial_match: &str) -> String {
         No result for {}.
         Did you mean  {}?
         ",
-        search_term.red(),
-        partial_match.yellow()
+        paint(search_term, Role::NotFound),
+        paint(partial_match, Role::DidYouMean)
     )
 }
@@ -0,0 +1,236 @@
+use anyhow::Result;
+use colored::ColoredString;
+use colored::Colorize;
+use serde_json::Value;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::io::BufRead;
+
+use utilities::colored_string_utils::*;
+
+use crate::dictionary_entry::DictionaryEntry;
+
+/// Number of problems of each kind listed with their line
+const EXAMPLES_PER_KIND: usize = 5;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ProblemKind {
+    ParseFailure,
+    EmptyWord,
+    EmptySenses,
+    Duplicate,
+}
+
+impl ProblemKind {
+    /// Duplicates are reported, but don't count against the error budget
+    pub fn is_error(&self) -> bool {
+        !matches!(self, ProblemKind::Duplicate)
+    }
+}
+
+impl fmt::Display for ProblemKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProblemKind::ParseFailure => write!(f, "parse failures"),
+            ProblemKind::EmptyWord => write!(f, "empty word"),
+            ProblemKind::EmptySenses => write!(f, "empty senses"),
+            ProblemKind::Duplicate => write!(f, "duplicates"),
+        }
+    }
+}
+
+pub struct Problem {
+    /// Counted from one like in an editor
+    pub line: usize,
+    pub kind: ProblemKind,
+    pub detail: String,
+}
+
+#[derive(Default)]
+pub struct ValidationReport {
+    pub lines: usize,
+    /// Lines that are a JSON object, whether or not they are a valid entry
+    pub objects: usize,
+    pub problems: BTreeMap<ProblemKind, usize>,
+    /// Lines with at least one error, a line with several errors counts once
+    pub failed_lines: usize,
+    last_failed_line: Option<usize>,
+    pub examples: Vec<Problem>,
+    /// Number of objects with a non-empty value for each top level field
+    pub field_coverage: BTreeMap<String, usize>,
+    /// Number of entries with each field that `DictionaryEntry` doesn't model,
+    /// nested fields by their path like `senses.topics`
+    pub unmodelled_fields: BTreeMap<String, usize>,
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.to_pretty_string())
+    }
+}
+
+impl ValidationReport {
+    /// Streams an extract and checks every line, nothing is kept per entry
+    /// but a hash to find duplicates.
+    pub fn validate(reader: impl BufRead) -> Result<Self> {
+        let mut report = ValidationReport::default();
+        let mut seen_entries: HashSet<u64> = HashSet::new();
+        for (i, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            report.lines += 1;
+            report.check_line(i + 1, &line, &mut seen_entries);
+        }
+        Ok(report)
+    }
+
+    fn check_line(&mut self, line_number: usize, line: &str, seen_entries: &mut HashSet<u64>) {
+        let value: Value = match serde_json::from_str(line) {
+            Ok(value) => value,
+            Err(err) => {
+                return self.report(line_number, ProblemKind::ParseFailure, err.to_string())
+            }
+        };
+        if let Some(object) = value.as_object() {
+            self.objects += 1;
+            for (field, field_value) in object {
+                if !is_empty(field_value) {
+                    *self.field_coverage.entry(field.clone()).or_default() += 1;
+                }
+            }
+        }
+        let entry: DictionaryEntry = match serde_json::from_value(value) {
+            Ok(entry) => entry,
+            Err(err) => {
+                return self.report(line_number, ProblemKind::ParseFailure, err.to_string())
+            }
+        };
+        for field in entry.unmodelled_fields() {
+            *self.unmodelled_fields.entry(field).or_default() += 1;
+        }
+        if entry.word.trim().is_empty() {
+            self.report(line_number, ProblemKind::EmptyWord, entry.pos.clone());
+        }
+        if entry.senses.is_empty() {
+            self.report(line_number, ProblemKind::EmptySenses, entry.word.clone());
+        }
+        if !seen_entries.insert(identity_hash(&entry)) {
+            self.report(
+                line_number,
+                ProblemKind::Duplicate,
+                format!("{} ({})", entry.word, entry.pos),
+            );
+        }
+    }
+
+    fn report(&mut self, line: usize, kind: ProblemKind, detail: String) {
+        if kind.is_error() && self.last_failed_line != Some(line) {
+            self.failed_lines += 1;
+            self.last_failed_line = Some(line);
+        }
+        let count = self.problems.entry(kind).or_default();
+        *count += 1;
+        if *count <= EXAMPLES_PER_KIND {
+            self.examples.push(Problem { line, kind, detail });
+        }
+    }
+
+    /// Percentage of lines with an error
+    pub fn error_rate(&self) -> f64 {
+        percentage(self.failed_lines, self.lines)
+    }
+
+    fn to_pretty_string(&self) -> ColoredString {
+        let mut res: Vec<ColoredString> = Vec::new();
+        res.push(format!("{} {}", "lines".bold(), format_integer(self.lines)).normal());
+        res.push("Problems".bold());
+        if self.problems.is_empty() {
+            res.push(" none".green());
+        }
+        for (kind, count) in &self.problems {
+            res.push(
+                format!(
+                    " {:<16}: {}",
+                    kind.to_string().red(),
+                    format_integer(*count)
+                )
+                .normal(),
+            );
+            for example in self.examples.iter().filter(|example| example.kind == *kind) {
+                res.push(format!("   line {}: {}", example.line, example.detail).dimmed());
+            }
+        }
+        res.push("Field coverage".bold());
+        for (field, count) in &self.field_coverage {
+            let unmodelled = if self.unmodelled_fields.contains_key(field) {
+                " (unmodelled)".yellow()
+            } else {
+                "".normal()
+            };
+            res.push(
+                format!(
+                    " {:<20}: {:>6.2}%{}",
+                    field.green(),
+                    percentage(*count, self.objects),
+                    unmodelled
+                )
+                .normal(),
+            );
+        }
+        let nested_fields: Vec<(&String, &usize)> = self
+            .unmodelled_fields
+            .iter()
+            .filter(|(field, _)| field.contains('.'))
+            .collect();
+        if !nested_fields.is_empty() {
+            res.push("Unmodelled nested fields".bold());
+        }
+        for (field, count) in nested_fields {
+            res.push(
+                format!(
+                    " {:<20}: {:>6.2}%",
+                    field.yellow(),
+                    percentage(*count, self.objects)
+                )
+                .normal(),
+            );
+        }
+        res.push(format!("{} {:.2}%", "error rate".bold(), self.error_rate()).normal());
+        NEWLINE.normal().join(res)
+    }
+}
+
+fn percentage(count: usize, total: usize) -> f64 {
+    if total == 0 {
+        return 0.0;
+    }
+    count as f64 * 100.0 / total as f64
+}
+
+fn is_empty(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::String(string) => string.is_empty(),
+        Value::Array(array) => array.is_empty(),
+        Value::Object(object) => object.is_empty(),
+        _ => false,
+    }
+}
+
+/// Entries are the same when they describe the same word, part of speech,
+/// etymology and glosses, their ids in CouchDB don't matter.
+fn identity_hash(entry: &DictionaryEntry) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    entry.lang_code.hash(&mut hasher);
+    entry.word.hash(&mut hasher);
+    entry.pos.hash(&mut hasher);
+    entry.etymology_number.hash(&mut hasher);
+    for sense in &entry.senses {
+        sense.glosses.hash(&mut hasher);
+    }
+    hasher.finish()
+}
@@ -79,6 +79,13 @@ impl DictionarySearchChannel {
     }
 }
 
+pub struct Statistics {
+    /// Number of buckets, that is languages, in the collection
+    pub buckets: u64,
+    /// Number of indexed words of the language
+    pub objects: u64,
+}
+
 pub struct DictionaryIngestChannel {
     language: Language,
     ingest_channel: IngestChannel,
@@ -100,15 +107,14 @@ impl DictionaryIngestChannel {
         Ok(number_of_objects as u64)
     }
 
-    pub fn statistics(&self) -> Result<()> {
+    pub fn statistics(&self) -> Result<Statistics> {
         let bucket_count = self
             .ingest_channel
             .count(CountRequest::buckets(WIKTIONARY_COLLECTION))?;
-        dbg!(bucket_count);
-
-        let object_count = self.count()?;
-        dbg!(object_count);
-        Ok(())
+        Ok(Statistics {
+            buckets: bucket_count as u64,
+            objects: self.count()?,
+        })
     }
 
     pub fn flush(&self) -> Result<u64> {
@@ -138,7 +144,7 @@ fn to_sonic_language(language: &Language) -> Lang {
     }
 }
 
-fn sonic_host() -> String {
+pub(crate) fn sonic_host() -> String {
     env!("SONIC_HOST").to_string()
 }
 
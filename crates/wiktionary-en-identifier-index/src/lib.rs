@@ -2,31 +2,95 @@ use anyhow::{bail, Result};
 use std::path::Path;
 use utilities::file_utils;
 use utilities::language::Language;
+use utilities::retry::RetryPolicy;
 
 mod channel;
+pub use crate::channel::Statistics;
 use crate::channel::{DictionaryIngestChannel, DictionarySearchChannel};
 
 pub mod indexing_stream;
 use crate::indexing_stream::*;
 
-pub fn statistics(language: &Language) -> Result<()> {
-    let ingest_channel = DictionaryIngestChannel::init(language)?;
-    ingest_channel.statistics()
+pub async fn statistics(language: &Language, retry_policy: &RetryPolicy) -> Result<Statistics> {
+    let language = *language;
+    run_with_retry_policy(retry_policy, move || {
+        DictionaryIngestChannel::init(&language)?.statistics()
+    })
+    .await
 }
 
-pub fn suggest(language: &Language, search_term: &str) -> Result<Vec<String>> {
-    let search_channel = DictionarySearchChannel::init(language)?;
-    search_channel.suggest(search_term)
+pub async fn suggest(
+    language: &Language,
+    search_term: &str,
+    retry_policy: &RetryPolicy,
+) -> Result<Vec<String>> {
+    let (language, search_term) = (*language, search_term.to_string());
+    run_with_retry_policy(retry_policy, move || {
+        DictionarySearchChannel::init(&language)?.suggest(&search_term)
+    })
+    .await
+}
+
+pub async fn query(
+    language: &Language,
+    search_term: &str,
+    retry_policy: &RetryPolicy,
+) -> Result<Vec<String>> {
+    let (language, search_term) = (*language, search_term.to_string());
+    run_with_retry_policy(retry_policy, move || {
+        DictionarySearchChannel::init(&language)?.query(&search_term)
+    })
+    .await
+}
+
+pub async fn did_you_mean(
+    language: &Language,
+    search_term: &str,
+    retry_policy: &RetryPolicy,
+) -> Result<Option<String>> {
+    let (language, search_term) = (*language, search_term.to_string());
+    run_with_retry_policy(retry_policy, move || {
+        DictionarySearchChannel::init(&language)?.did_you_mean(&search_term)
+    })
+    .await
 }
 
-pub fn query(language: &Language, search_term: &str) -> Result<Vec<String>> {
-    let search_channel = DictionarySearchChannel::init(language)?;
-    search_channel.query(search_term)
+/// sonic-channel has no timeouts and blocks, so every attempt runs on the
+/// blocking thread pool and isn't waited for when it doesn't answer in time.
+/// The thread keeps blocking until sonic answers, so the runtime has to be
+/// shut down with `shutdown_background` to not wait for it at exit.
+async fn run_with_retry_policy<T, F>(retry_policy: &RetryPolicy, operation: F) -> Result<T>
+where
+    T: Send + 'static,
+    F: Fn() -> Result<T> + Clone + Send + 'static,
+{
+    // sonic fails with its own errors, none of which is worth keeping from a retry
+    retry_policy
+        .retry_async(
+            |_| true,
+            || {
+                let operation = operation.clone();
+                async move {
+                    match tokio::time::timeout(
+                        retry_policy.timeout,
+                        tokio::task::spawn_blocking(operation),
+                    )
+                    .await
+                    {
+                        Ok(result) => result?,
+                        Err(_) => bail!(
+                            "sonic didn't answer within {} seconds",
+                            retry_policy.timeout.as_secs_f64()
+                        ),
+                    }
+                }
+            },
+        )
+        .await
 }
 
-pub fn did_you_mean(language: &Language, search_term: &str) -> Result<Option<String>> {
-    let search_channel = DictionarySearchChannel::init(language)?;
-    search_channel.did_you_mean(search_term)
+pub fn host() -> String {
+    channel::sonic_host()
 }
 
 pub fn generate_indices(
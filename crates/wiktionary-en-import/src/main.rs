@@ -5,8 +5,9 @@ use utilities::language::Language;
 
 use wiktionary_en_db::client::DbClient;
 use wiktionary_en_download::Downloader;
+use wiktionary_en_entities::validation::ValidationReport;
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 
 #[cfg(feature = "sonic")]
 mod indexing_executor;
@@ -31,6 +32,18 @@ struct Cli {
     /// Download a wiktionary extract from the web
     #[clap(long, short = 'x')]
     download: bool,
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Check an extract before importing it
+    Validate {
+        /// Percentage of lines that may fail validation before it fails
+        #[clap(long, default_value = "1.0")]
+        error_budget: f64,
+    },
 }
 
 async fn import_wiktionary_extract(path: &Path, language: &Language, force: bool) -> Result<usize> {
@@ -52,6 +65,19 @@ async fn main() -> Result<()> {
         .or_use_config_or_default(args.language);
 
     let db_path: PathBuf = file_utils::get_db_path(args.db_path, &language_to_use);
+    if let Some(Command::Validate { error_budget }) = args.command {
+        let report = ValidationReport::validate(file_utils::get_file_reader(&db_path)?)?;
+        utilities::pager::print_in_pager(&report.to_string())?;
+        if report.error_rate() > error_budget {
+            bail!(
+                "{} of {} lines failed validation, that is more than the error budget of {}%",
+                report.failed_lines,
+                report.lines,
+                error_budget
+            );
+        }
+        return Ok(());
+    }
     #[cfg(feature = "sonic")]
     if args.create_index {
         let stream = wiktionary_en_identifier_index::generate_indices(
@@ -6,10 +6,12 @@ use std::any::type_name;
 use std::fmt;
 use std::fmt::{Display, Formatter};
 use utilities::colored_string_utils;
+use utilities::theme::{self, Role};
 use utilities::DICTIONARY_EXTENSIONS;
 use utilities::LUA_DIR;
 use wiktionary_en_db::client::DbClientMutex;
-use wiktionary_en_entities::dictionary_entry::DictionaryEntry;
+use wiktionary_en_entities::dictionary_entry::{DictionaryEntry, Form};
+use wiktionary_en_entities::render::RenderFormat;
 use wiktionary_en_entities::result::{DictionaryResult, DidYouMean};
 
 const LUA_EXTENSION_ERROR: &str = "Lua Extension Error";
@@ -185,6 +187,48 @@ impl ExtensionHandler {
         }
     }
 
+    /// Names of the inner workings hooks defined in `Extensions`.
+    pub fn hooks(&self) -> Result<Vec<String>> {
+        let names = self.extension_names()?;
+        Ok(names
+            .into_iter()
+            .filter(|name| InnerWorkingsExtension::as_strings().contains(name))
+            .collect())
+    }
+
+    /// Names of the extensions in `Extensions` that can be called directly.
+    pub fn extensions(&self) -> Result<Vec<String>> {
+        let names = self.extension_names()?;
+        Ok(names
+            .into_iter()
+            .filter(|name| !InnerWorkingsExtension::as_strings().contains(name))
+            .collect())
+    }
+
+    fn extension_names(&self) -> Result<Vec<String>> {
+        let extensions = match get_extensions_as_lua_value(&self.lua) {
+            Ok(Value::Table(extensions)) => extensions,
+            Ok(Value::Nil) => return Ok(Vec::new()),
+            Ok(value) => bail!("'Extensions' must be a table, not a {}", value.type_name()),
+            Err(err) => return Err(anyhow!("{}", err).context(LUA_EXTENSION_ERROR)),
+        };
+        let mut names = Vec::new();
+        for pair in extensions.pairs::<String, Value>() {
+            let (name, value) =
+                pair.map_err(|err| anyhow!("{}", err).context(LUA_EXTENSION_ERROR))?;
+            if !value.is_function() {
+                bail!(
+                    "the extension '{}' must be a function, not a {}",
+                    name,
+                    value.type_name()
+                );
+            }
+            names.push(name);
+        }
+        names.sort();
+        Ok(names)
+    }
+
     pub async fn call_extension<T>(
         &self,
         extension_name: &str,
@@ -240,22 +284,35 @@ fn create_importable_lua_module(
     loaded.set(package_name, module)
 }
 
-fn load_lua_api(lua: &Lua) -> mlua::Result<()> {
+/// Makes `wiktionary_api` importable with `require`
+pub fn load_lua_api(lua: &Lua) -> mlua::Result<()> {
     let wiktionary_api = lua.create_table()?;
     let apply_color_fn = apply_color(lua)?;
     wiktionary_api.set("apply_color", apply_color_fn)?;
     let apply_style_fn = apply_style(lua)?;
     wiktionary_api.set("apply_style", apply_style_fn)?;
+    let apply_theme_fn = apply_theme(lua)?;
+    wiktionary_api.set("apply_theme", apply_theme_fn)?;
     let horizontal_line_fn = horizontal_line(lua)?;
     wiktionary_api.set("horizontal_line", horizontal_line_fn)?;
     let to_pretty_string_fn = to_pretty_string(lua)?;
     wiktionary_api.set("to_pretty_string", to_pretty_string_fn)?;
+    let terminal_width_fn = terminal_width(lua)?;
+    wiktionary_api.set("terminal_width", terminal_width_fn)?;
     let wrap_text_at_fn = wrap_text_at(lua)?;
     wiktionary_api.set("wrap_text_at", wrap_text_at_fn)?;
     let indent_fn = indent(lua)?;
     wiktionary_api.set("indent", indent_fn)?;
     let project_folder_fn = project_folder(lua)?;
     wiktionary_api.set("project_folder", project_folder_fn)?;
+    let categories_fn = categories(lua)?;
+    wiktionary_api.set("categories", categories_fn)?;
+    let syllables_fn = syllables(lua)?;
+    wiktionary_api.set("syllables", syllables_fn)?;
+    let is_word_form_fn = is_word_form(lua)?;
+    wiktionary_api.set("is_word_form", is_word_form_fn)?;
+    let render_fn = render(lua)?;
+    wiktionary_api.set("render", render_fn)?;
 
     create_importable_lua_module(lua, "wiktionary_api", wiktionary_api)
 }
@@ -327,21 +384,23 @@ async fn format_did_you_mean_banner(
 
 fn apply_color(lua: &Lua) -> mlua::Result<Function> {
     lua.create_function(|_, (text, color): (String, String)| {
-        let colored_text = match color.to_lowercase().as_str() {
-            "red" => text.red(),
-            "green" => text.green(),
-            "blue" => text.blue(),
-            "yellow" => text.yellow(),
-            "cyan" => text.cyan(),
-            "magenta" => text.magenta(),
-            "white" => text.white(),
-            "black" => text.black(),
-            _ => text.into(), // Default to the original text if color is unknown
+        let colored_text = match theme::parse_color(&color) {
+            Ok(color) => text.color(color),
+            Err(_) => text.into(), // Default to the original text if color is unknown
         };
         Ok(colored_text.to_string())
     })
 }
 
+fn apply_theme(lua: &Lua) -> mlua::Result<Function> {
+    lua.create_function(|_, (text, role): (String, String)| {
+        let role: Role = role
+            .parse()
+            .map_err(|err: anyhow::Error| mlua::Error::RuntimeError(err.to_string()))?;
+        Ok(theme::paint(&text, role).to_string())
+    })
+}
+
 fn apply_style(lua: &Lua) -> mlua::Result<Function> {
     lua.create_function(|_, (text, style): (String, String)| {
         let style_text = match style.to_lowercase().as_str() {
@@ -368,6 +427,10 @@ fn horizontal_line(lua: &Lua) -> mlua::Result<Function> {
     })
 }
 
+fn terminal_width(lua: &Lua) -> mlua::Result<Function> {
+    lua.create_function(|_, ()| Ok(colored_string_utils::line_width()))
+}
+
 fn wrap_text_at(lua: &Lua) -> mlua::Result<Function> {
     lua.create_function(|_, (text, width): (String, usize)| {
         Ok(colored_string_utils::wrap(&text.into(), width).to_string())
@@ -386,6 +449,35 @@ fn to_pretty_string(lua: &Lua) -> mlua::Result<Function> {
     })
 }
 
+fn categories(lua: &Lua) -> mlua::Result<Function> {
+    lua.create_function(|_, dictionary_entry: DictionaryEntry| {
+        Ok(dictionary_entry
+            .category_names()
+            .into_iter()
+            .map(String::from)
+            .collect::<Vec<String>>())
+    })
+}
+
+fn syllables(lua: &Lua) -> mlua::Result<Function> {
+    lua.create_function(|_, dictionary_entry: DictionaryEntry| {
+        Ok(dictionary_entry.syllables().to_vec())
+    })
+}
+
+fn is_word_form(lua: &Lua) -> mlua::Result<Function> {
+    lua.create_function(|_, form: Form| Ok(form.is_word_form()))
+}
+
+fn render(lua: &Lua) -> mlua::Result<Function> {
+    lua.create_function(|_, (dictionary_entry, format): (DictionaryEntry, String)| {
+        let format: RenderFormat = format
+            .parse()
+            .map_err(|err: anyhow::Error| mlua::Error::RuntimeError(err.to_string()))?;
+        Ok(dictionary_entry.render(format))
+    })
+}
+
 fn project_folder(lua: &Lua) -> mlua::Result<Function> {
     lua.create_function(|_, ()| Ok(LUA_DIR!()))
 }
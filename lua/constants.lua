@@ -1,7 +1,19 @@
+local api = require("wiktionary_api")
+
+local values = {
+  -- follows the terminal, or Config.width when it's set
+  max_line_width = function()
+    return api.terminal_width()
+  end,
+}
+
 local constants = setmetatable({}, {
-  __index = {
-    max_line_width = 80,
-  },
+  __index = function(_, key)
+    local value = values[key]
+    if value then
+      return value()
+    end
+  end,
   __newindex = function()
     error("read only value")
   end,
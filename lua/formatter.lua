@@ -14,8 +14,8 @@ local function style_italic_dimmed(text, length)
   return api.apply_style(api.apply_style(utils.leftpad(text, length), "italic"), "dimmed")
 end
 
-local function style_bold_with_color(text, color)
-  return api.apply_color(api.apply_style(text, "bold"), color)
+local function style_section_title(text)
+  return api.apply_theme(api.apply_style(text, "bold"), "section_title")
 end
 
 local function format_etymology(etymology_text)
@@ -23,7 +23,7 @@ local function format_etymology(etymology_text)
     return nil
   end
   local list = {}
-  table.insert(list, api.apply_style("Etymology:", "bold"))
+  table.insert(list, style_section_title("Etymology:"))
   table.insert(list, api.wrap_text_at(etymology_text, constants.max_line_width))
   return table.concat(list, "\n")
 end
@@ -71,11 +71,24 @@ local function format_sounds(sounds)
     return nil
   end
 
-  table.insert(result, api.apply_style("Pronunciation", "bold"))
+  table.insert(result, style_section_title("Pronunciation"))
   table.insert(result, table.concat(as_strings, "\n"))
   return table.concat(result, "\n")
 end
 
+local function format_forms(forms)
+  local word_forms = utils.filter(forms or {}, api.is_word_form)
+  if utils.is_empty(word_forms) then
+    return nil
+  end
+  local result = {}
+  table.insert(result, style_section_title("Forms"))
+  for _, v in ipairs(word_forms) do
+    table.insert(result, string.format(" %s %s", v.form, api.apply_style(format_tags(v.tags), "italic")))
+  end
+  return table.concat(result, "\n")
+end
+
 local function translations_to_strings(translations)
   local result = {}
 
@@ -102,7 +115,7 @@ local function format_translations(translations)
     return nil
   end
   local list = {}
-  table.insert(list, api.apply_style("Translations:", "bold"))
+  table.insert(list, style_section_title("Translations:"))
   table.insert(list, table.concat(translations_to_strings(filtered_translations), "\n"))
   return table.concat(list, "\n")
 end
@@ -116,7 +129,7 @@ local function examples_to_strings(examples)
   for i, v in ipairs(examples) do
     if v.text then
       local formatted =
-        string.format("%s. %s", style_italic_dimmed(i), api.wrap_text_at(v.text, constants.max_line_width - 1))
+        string.format("%s. %s", api.apply_theme(style_italic_dimmed(i), "example_index"), api.wrap_text_at(v.text, constants.max_line_width - 1))
       table.insert(result, api.indent(formatted))
     end
   end
@@ -128,14 +141,53 @@ local function format_examples(examples)
   return table.concat(examples_to_strings(examples), "\n")
 end
 
+local function raw_gloss_qualifiers(raw_glosses)
+  local result = {}
+  for _, raw_gloss in ipairs(raw_glosses or {}) do
+    local qualifier = string.match(raw_gloss, "^%((.-)%)")
+    if qualifier then
+      table.insert(result, qualifier)
+    end
+  end
+  return result
+end
+
+local function format_sense_qualifiers(sense)
+  local qualifiers = raw_gloss_qualifiers(sense.raw_glosses)
+  if utils.is_empty(qualifiers) then
+    return format_tags(sense.tags)
+  end
+  return string.format("(%s)", table.concat(qualifiers, "; "))
+end
+
+local function format_cross_references(links)
+  local words = {}
+  -- links are { text, target } pairs, targets may point to a section such as "meaning#Noun"
+  for _, link in ipairs(links or {}) do
+    local target_word = string.match(link[2] or link[1], "^[^#]*")
+    if target_word ~= "" and not utils.has_value(words, target_word) then
+      table.insert(words, target_word)
+    end
+  end
+  if utils.is_empty(words) then
+    return nil
+  end
+  local see = api.wrap_text_at("See: " .. table.concat(words, ", "), constants.max_line_width)
+  return api.apply_style(see, "dimmed")
+end
+
 local function format_sense(sense, i)
   local result = {}
-  local title = string.format("%s. %s", api.apply_style(i, "bold"), api.apply_style(format_tags(sense.tags), "bold"))
+  local title = string.format("%s. %s", api.apply_style(i, "bold"), api.apply_style(format_sense_qualifiers(sense), "bold"))
   table.insert(result, title)
   table.insert(result, api.wrap_text_at(format_glosses(sense.glosses), constants.max_line_width))
   if not utils.is_empty(sense.examples) then
     table.insert(result, format_examples(sense.examples))
   end
+  local cross_references = format_cross_references(sense.links)
+  if cross_references then
+    table.insert(result, cross_references)
+  end
   return table.concat(result, "\n")
 end
 
@@ -183,18 +235,25 @@ local function format_related_words(related_words, category_title)
     return nil
   end
   local list = {}
-  table.insert(list, api.apply_style(category_title, "bold"))
+  table.insert(list, style_section_title(category_title))
   table.insert(list, table.concat(related_words_to_strings(related_words, calculate_pad_size(related_words)), "\n"))
   return table.concat(list, "\n")
 end
 
+-- set once the history couldn't be read, so that an unavailable CouchDB is only waited for once
+local history_unavailable = false
+
 local function history(word)
   local query = {
     word = word,
   }
-  local entry = db_client:find_one_in_collection(features.history.name, query)
-  if entry then
-    return entry
+  if not history_unavailable then
+    local ok, entry = pcall(db_client.find_one_in_collection, db_client, features.history.name, query)
+    if not ok then
+      history_unavailable = true
+    elseif entry then
+      return entry
+    end
   end
   return {
     last_seen_at = os.time(),
@@ -204,17 +263,31 @@ end
 
 -------------------------------------------------------------------------------
 
-local function format_left_side_header(word, pos)
-  return string.format("%s (%s)", word, pos)
+local function hyphenations_to_string(hyphenations)
+  local as_strings = {}
+  for _, hyphenation in ipairs(hyphenations or {}) do
+    table.insert(as_strings, table.concat(hyphenation.parts, "‧"))
+  end
+  return table.concat(as_strings, ", ")
 end
 
-local function format_header(word, pos)
+local function format_left_side_header(word, pos, hyphenations)
+  if hyphenations == "" then
+    return string.format("%s (%s)", word, pos)
+  end
+  return string.format("%s (%s) %s", word, pos, hyphenations)
+end
+
+local function format_header(word, pos, hyphenations)
   local history_of_word = history(word)
   local last_seen = utils.format_date(history_of_word.last_seen_at)
 
-  local colored_word = api.apply_color(word, "cyan")
-  local left = format_left_side_header(colored_word, pos)
-  local left_width = #format_left_side_header(word, pos)
+  local colored_word = api.apply_theme(word, "headword")
+  local colored_pos = api.apply_theme(pos, "pos")
+  local hyphenation_text = hyphenations_to_string(hyphenations)
+  local styled_hyphenations = hyphenation_text == "" and "" or api.apply_style(hyphenation_text, "dimmed")
+  local left = format_left_side_header(colored_word, colored_pos, styled_hyphenations)
+  local left_width = utf8.len(format_left_side_header(word, pos, hyphenation_text))
   local right = string.format("Last seen: %s (%s)", last_seen, history_of_word.count)
   local total_width = constants.max_line_width
 
@@ -225,7 +298,7 @@ end
 local function format_entry(entry)
   local content = {}
 
-  local formatted_etymology = format_etymology(entry.etymology)
+  local formatted_etymology = format_etymology(entry.etymology_text)
   if formatted_etymology then
     table.insert(content, formatted_etymology)
   end
@@ -233,6 +306,10 @@ local function format_entry(entry)
   if formatted_sounds then
     table.insert(content, formatted_sounds)
   end
+  local formatted_forms = format_forms(entry.forms)
+  if formatted_forms then
+    table.insert(content, formatted_forms)
+  end
   local formatted_senses = format_senses(entry.senses)
   if formatted_senses then
     table.insert(content, formatted_senses)
@@ -258,7 +335,7 @@ local function format_entry(entry)
 %s
 	]],
     horizontal_line,
-    format_header(entry.word, entry.pos),
+    format_header(entry.word, entry.pos, entry.hyphenations),
     horizontal_line,
     table.concat(content, string.format("\n%s\n", horizontal_line))
   )
@@ -266,9 +343,9 @@ end
 
 local function format_did_you_mean_banner(did_you_mean)
   local content = {}
-  local searched_for_styled = api.apply_style(style_bold_with_color(did_you_mean.searched_for, "red"), "strikethrough")
+  local searched_for_styled = api.apply_style(api.apply_theme(api.apply_style(did_you_mean.searched_for, "bold"), "not_found"), "strikethrough")
   local searched_for_msg = string.format("No results found for %s.", searched_for_styled)
-  local did_you_mean_msg = string.format("Did you mean %s?", style_bold_with_color(did_you_mean.suggestion, "yellow"))
+  local did_you_mean_msg = string.format("Did you mean %s?", api.apply_theme(api.apply_style(did_you_mean.suggestion, "bold"), "did_you_mean"))
   table.insert(content, searched_for_msg)
   table.insert(content, did_you_mean_msg)
   return table.concat(content, "\n")
@@ -6,39 +6,74 @@ local features = require("features")
 
 local history = {}
 
+local number_of_recent_entries = 20
+
 local function format_history_entry(history_entry)
   local last_seen = utils.format_date(history_entry.last_seen_at)
   local word = history_entry.word
-  local colored_word = api.apply_color(word, "cyan")
+  local colored_word = api.apply_theme(word, "headword")
   local left_width = #word
   local right = string.format("Last seen: %s count: %s", last_seen, history_entry.count)
   local total_width = constants.max_line_width
 
-  local padding_length = math.max(total_width / 2 - left_width, 1)
+  local padding_length = math.max(total_width // 2 - left_width, 1)
   return colored_word .. string.rep(" ", padding_length) .. right
 end
 
-local function format_all_entries()
+local function format_entries(documents)
   local formatted_entries = {}
-  local documents = db_client:find_in_collection(features.history.name, {})
-
   for _, entry in ipairs(documents) do
     table.insert(formatted_entries, format_history_entry(entry))
   end
-
   return { result = table.concat(formatted_entries, "\n") }
 end
 
+local function format_all_entries()
+  local documents = db_client:find_in_collection(features.history.name, {})
+  return format_entries(documents)
+end
+
+local function format_recent_entries()
+  local query = { now_seen_at = { ["$gt"] = 0 } }
+  local options = {
+    sort = { { now_seen_at = "desc" } },
+    limit = number_of_recent_entries,
+  }
+  local documents = db_client:find_in_collection(features.history.name, query, options)
+  return format_entries(documents)
+end
+
 local function delete()
   local query = {}
-  local count = db_client:delete_in_collection(features.history.name, query)
-  return { result = string.format("deleted %s history entries", count) }
+  local deleted, failures = db_client:delete_in_collection(features.history.name, query)
+  if not utils.is_empty(failures) then
+    local failed_ids = {}
+    for _, failure in ipairs(failures) do
+      table.insert(failed_ids, string.format("%s (%s)", failure.id, failure.error))
+    end
+    local error_msg = string.format(
+      "deleted %s history entries, failed to delete %s: %s",
+      deleted,
+      #failures,
+      table.concat(failed_ids, ", ")
+    )
+    return { result = error_msg, error = "partial_delete" }
+  end
+  return { result = string.format("deleted %s history entries", deleted) }
 end
 
 local function index()
-  local keys = { word = 1 }
-  db_client:create_index_for_collection(features.history.name, keys)
-  return { result = "index created on key 'word'" }
+  local indexes = {
+    { name = "word", fields = { "word" } },
+    { name = "recent", fields = { { now_seen_at = "asc" } } },
+  }
+  local created = {}
+  for _, definition in ipairs(indexes) do
+    local result = db_client:create_index_for_collection(features.history.name, definition)
+    local state = result.created and "created" or "exists"
+    table.insert(created, string.format("index '%s' %s", definition.name, state))
+  end
+  return { result = table.concat(created, "\n") }
 end
 
 local function count()
@@ -81,6 +116,8 @@ history.main = function(options)
         return delete()
       elseif option == "index" then
         return index()
+      elseif option == "recent" then
+        return format_recent_entries()
       elseif option == "count" then
         return count()
       elseif option == "create_count_view" then
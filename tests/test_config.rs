@@ -1,8 +1,12 @@
 #[cfg(test)]
 mod tests {
     use anyhow::Result;
+    use colored::Color;
+    use mlua::Lua;
     use tracing::info;
     use tracing_test::traced_test;
+    use utilities::theme::Role;
+    use wiktionary_en_entities::config::Config;
     use wiktionary_en_lua::config::ConfigHandler;
 
     #[traced_test]
@@ -12,4 +16,20 @@ mod tests {
         info!("language configured: {}", config_handler.config.language);
         Ok(())
     }
+
+    #[test]
+    fn theme_roles_of_the_config() -> mlua::Result<()> {
+        let lua = Lua::new();
+        let config: Config = lua
+            .load(r#"return { language = "en", theme = { preset = "light", pos = 208 } }"#)
+            .eval()?;
+        assert_eq!(config.theme.color(Role::Pos), Some(Color::AnsiColor(208)));
+        let err = lua
+            .load(r#"return { language = "en", theme = { headwrod = "red" } }"#)
+            .eval::<Config>()
+            .err()
+            .map(|err| err.to_string());
+        assert!(err.is_some_and(|err| err.contains("unknown theme role 'headwrod'")));
+        Ok(())
+    }
 }
@@ -1,11 +1,16 @@
 #[cfg(test)]
 mod tests {
     use anyhow::Result;
+    use futures_util::{StreamExt, TryStreamExt};
     use rstest::{fixture, rstest};
+    use serde_json::{json, Value};
     use std::env;
+    use std::pin::pin;
     use utilities::file_utils;
     use utilities::language::Language;
-    use wiktionary_en_db::client::DbClient;
+    use wiktionary_en_db::client::{
+        CategoryCount, DbClient, Document, ExtensionCollectionIndex, ImportMode, Pagination,
+    };
 
     mod common {
         include!("common/couchdb_container.rs");
@@ -63,8 +68,128 @@ mod tests {
         let mut client = awaited_test_setup.db_client;
         client.create_analytics().await?;
         insert_test_data(&mut client).await?;
-        let entries = client.find_by_word("dictionary").await?;
+        let page = client
+            .find_by_word("dictionary", &Pagination::default())
+            .await?;
+        assert_eq!(page.hits.len(), 1);
+        Ok(())
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn find_by_words(
+        #[from(test_setup)]
+        #[future]
+        test_setup: TestSetup,
+    ) -> Result<()> {
+        let awaited_test_setup = test_setup.await;
+        let mut client = awaited_test_setup.db_client;
+        insert_test_data(&mut client).await?;
+        let entries = client
+            .find_by_words(&["dictionary".to_string(), "lexicon".to_string()])
+            .await?;
         assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].word, "dictionary");
+        assert!(client.find_by_words(&[]).await?.is_empty());
+        Ok(())
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn find_by_word_beyond_last_page(
+        #[from(test_setup)]
+        #[future]
+        test_setup: TestSetup,
+    ) -> Result<()> {
+        let awaited_test_setup = test_setup.await;
+        let mut client = awaited_test_setup.db_client;
+        client.create_analytics().await?;
+        insert_test_data(&mut client).await?;
+        let pagination = Pagination::page(2, 1);
+        let page = client.find_by_word("dictionary", &pagination).await?;
+        assert!(page.hits.is_empty());
+        assert_eq!(page.total, 1);
+        assert_eq!(page.remaining(&pagination), 0);
+        Ok(())
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn find_by_form(
+        #[from(test_setup)]
+        #[future]
+        test_setup: TestSetup,
+    ) -> Result<()> {
+        let awaited_test_setup = test_setup.await;
+        let mut client = awaited_test_setup.db_client;
+        insert_test_data(&mut client).await?;
+        let page = client
+            .find_by_form("dictionaries", &Pagination::default())
+            .await?;
+        assert_eq!(page.hits.len(), 1);
+        assert_eq!(page.hits[0].word, "dictionary");
+        assert_eq!(page.form_of.len(), 1);
+        assert_eq!(page.form_of[0].tags, vec!["plural".to_string()]);
+        let page = client
+            .find_by_form("dictionary", &Pagination::default())
+            .await?;
+        assert!(page.hits.is_empty());
+        Ok(())
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn find_by_syllable_count(
+        #[from(test_setup)]
+        #[future]
+        test_setup: TestSetup,
+    ) -> Result<()> {
+        let awaited_test_setup = test_setup.await;
+        let mut client = awaited_test_setup.db_client;
+        insert_test_data(&mut client).await?;
+        let page = client
+            .find_by_syllable_count(4, None, &Pagination::default())
+            .await?;
+        assert_eq!(page.hits.len(), 1);
+        assert_eq!(page.hits[0].word, "dictionary");
+        let page = client
+            .find_by_syllable_count(3, Some("dictionary"), &Pagination::default())
+            .await?;
+        assert_eq!(page.hits.len(), 1);
+        let page = client
+            .find_by_syllable_count(2, Some("dictionary"), &Pagination::default())
+            .await?;
+        assert!(page.hits.is_empty());
+        Ok(())
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn find_by_category(
+        #[from(test_setup)]
+        #[future]
+        test_setup: TestSetup,
+    ) -> Result<()> {
+        let awaited_test_setup = test_setup.await;
+        let mut client = awaited_test_setup.db_client;
+        insert_test_data(&mut client).await?;
+        let categories = client.search_categories("DICTIONAR").await?;
+        assert_eq!(
+            categories,
+            vec![CategoryCount {
+                name: "Dictionaries".to_string(),
+                count: 1
+            }]
+        );
+        let members = client.category_members("Computing").await?;
+        assert_eq!(
+            members,
+            vec![CategoryCount {
+                name: "dictionary".to_string(),
+                count: 1
+            }]
+        );
+        assert!(client.category_members("Comput").await?.is_empty());
         Ok(())
     }
 
@@ -117,6 +242,33 @@ mod tests {
         Ok(())
     }
 
+    #[rstest]
+    #[tokio::test]
+    async fn import_stamp_changes_on_reimport(
+        #[from(test_setup)]
+        #[future]
+        test_setup: TestSetup,
+    ) -> Result<()> {
+        let awaited_test_setup = test_setup.await;
+        let mut client = awaited_test_setup.db_client;
+        client.create_analytics().await?;
+        assert_eq!(client.import_stamp().await?, None);
+
+        insert_test_data(&mut client).await?;
+        let first_stamp = client.import_stamp().await?;
+        assert!(first_stamp.is_some());
+
+        let path = file_utils::get_db_path(
+            Some("./data/wiktionary-en-test.jsonl".to_string()),
+            &Language::EN,
+        );
+        client
+            .insert_wiktionary_file(file_utils::get_file_reader(&path)?, true)
+            .await?;
+        assert_ne!(client.import_stamp().await?, first_stamp);
+        Ok(())
+    }
+
     #[rstest]
     #[tokio::test]
     async fn create_index_on_word(
@@ -129,4 +281,275 @@ mod tests {
         assert!(client.create_index_on_word().await?);
         Ok(())
     }
+
+    #[rstest]
+    #[tokio::test]
+    async fn bulk_update_and_delete_in_extension_collection(
+        #[from(test_setup)]
+        #[future]
+        test_setup: TestSetup,
+    ) -> Result<()> {
+        let awaited_test_setup = test_setup.await;
+        let client = awaited_test_setup.db_client;
+        let extension_name = "bulk";
+        for word in ["one", "two", "three"] {
+            client
+                .insert_one_into_extension_collection(
+                    extension_name,
+                    Document::from(json!({"word": word, "count": 1})),
+                )
+                .await?;
+        }
+
+        let updated = client
+            .update_many_in_extension_collection(
+                extension_name,
+                Document::from(json!({"count": 1})),
+                Document::from(json!({"count": 2})),
+            )
+            .await?;
+        assert_eq!(updated.count, 3);
+        assert!(updated.failures.is_empty());
+
+        let count = client
+            .count_in_extension_collection(extension_name, Document::from(json!({"count": 2})))
+            .await?;
+        assert_eq!(count, 3);
+
+        let deleted = client
+            .delete_many_in_extension_collection(extension_name, Document::from(json!({})))
+            .await?;
+        assert_eq!(deleted.count, 3);
+        assert!(deleted.failures.is_empty());
+        Ok(())
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn bulk_update_of_the_selected_field_over_many_pages(
+        #[from(test_setup)]
+        #[future]
+        test_setup: TestSetup,
+    ) -> Result<()> {
+        let awaited_test_setup = test_setup.await;
+        let client = awaited_test_setup.db_client;
+        let extension_name = "bulk_pages";
+        // more documents than fit into one batch of the bulk update
+        for i in 0..1200 {
+            client
+                .insert_one_into_extension_collection(
+                    extension_name,
+                    Document::from(json!({"word": format!("word{}", i), "count": 1})),
+                )
+                .await?;
+        }
+
+        let updated = client
+            .update_many_in_extension_collection(
+                extension_name,
+                Document::from(json!({"count": 1})),
+                Document::from(json!({"count": 2})),
+            )
+            .await?;
+        assert_eq!(updated.count, 1200);
+        let count = client
+            .count_in_extension_collection(extension_name, Document::from(json!({"count": 2})))
+            .await?;
+        assert_eq!(count, 1200);
+        Ok(())
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn create_list_and_drop_extension_collection_indexes(
+        #[from(test_setup)]
+        #[future]
+        test_setup: TestSetup,
+    ) -> Result<()> {
+        let awaited_test_setup = test_setup.await;
+        let client = awaited_test_setup.db_client;
+        let extension_name = "indexes";
+        let created = client
+            .create_index_for_extension_collection(
+                extension_name,
+                Document::from(json!({
+                    "name": "recent",
+                    "fields": [{"now_seen_at": "desc"}, {"word": "desc"}],
+                    "partial_filter_selector": {"count": {"$gt": 1}},
+                })),
+            )
+            .await?;
+        assert_eq!(created.document["created"], json!(true));
+
+        let indexes = client
+            .list_indexes_in_extension_collection(extension_name)
+            .await?;
+        let recent = indexes
+            .iter()
+            .find(|index| index.name == "recent")
+            .expect("index 'recent' should be listed");
+        assert_eq!(
+            recent.fields,
+            vec![
+                ("now_seen_at".to_string(), "desc".to_string()),
+                ("word".to_string(), "desc".to_string())
+            ]
+        );
+        assert!(recent.partial_filter_selector.is_some());
+
+        let dropped = client
+            .drop_index_in_extension_collection(extension_name, "recent")
+            .await?;
+        assert_eq!(dropped, 1);
+
+        let compound_plain_table = client
+            .create_index_for_extension_collection(
+                extension_name,
+                Document::from(json!({"word": 1, "count": 1})),
+            )
+            .await;
+        assert!(compound_plain_table.is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn describing_an_extension_collection_index() {
+        let index = ExtensionCollectionIndex {
+            name: "recent".to_string(),
+            ddoc: Some("_design/history".to_string()),
+            index_type: "json".to_string(),
+            fields: vec![
+                ("now_seen_at".to_string(), "desc".to_string()),
+                ("word".to_string(), "asc".to_string()),
+            ],
+            partial_filter_selector: None,
+        };
+        assert_eq!(
+            index.to_string(),
+            "recent (json) [now_seen_at desc, word asc]"
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn export_and_import_extension_collection(
+        #[from(test_setup)]
+        #[future]
+        test_setup: TestSetup,
+    ) -> Result<()> {
+        let awaited_test_setup = test_setup.await;
+        let client = awaited_test_setup.db_client;
+        let extension_name = "backup";
+        for word in ["one", "two"] {
+            client
+                .insert_one_into_extension_collection(
+                    extension_name,
+                    Document::from(json!({"word": word})),
+                )
+                .await?;
+        }
+        client
+            .create_view_in_extension_collection(
+                extension_name,
+                Document::from(json!({
+                    "document_name": "analytics",
+                    "view_name": "word_count",
+                    "map": "function(doc) { doc.word && emit(doc.word, 1); }",
+                    "reduce": "_count",
+                })),
+            )
+            .await?;
+
+        let documents: Vec<Value> = client
+            .export_extension_collection(extension_name)
+            .await?
+            .try_collect()
+            .await?;
+        assert_eq!(documents.len(), 3);
+        assert!(documents
+            .iter()
+            .all(|document| document.get("_rev").is_none()));
+
+        let other_client = DbClient::init(Language::DE).await?;
+        for _ in 0..2 {
+            let result = other_client
+                .import_into_extension_collection(
+                    extension_name,
+                    documents.clone(),
+                    &ImportMode::Overwrite,
+                )
+                .await?;
+            assert_eq!(result.imported, 3);
+        }
+        let result = other_client
+            .import_into_extension_collection(extension_name, documents, &ImportMode::Merge)
+            .await?;
+        assert_eq!(result.skipped, 3);
+
+        let invalid = other_client
+            .import_into_extension_collection(
+                extension_name,
+                vec![json!({"word": "four"}), json!("not a document")],
+                &ImportMode::Overwrite,
+            )
+            .await;
+        assert!(invalid.is_err());
+        let kept = other_client
+            .count_in_extension_collection(
+                extension_name,
+                Document::from(json!({"word": {"$exists": true}})),
+            )
+            .await?;
+        assert_eq!(kept, 2);
+
+        let view = other_client
+            .get_view_in_extension_collection(
+                extension_name,
+                Document::from(json!({"document_name": "analytics", "view_name": "word_count"})),
+            )
+            .await?;
+        assert_eq!(view.document["exists"], json!(true));
+        Ok(())
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn watch_extension_collection(
+        #[from(test_setup)]
+        #[future]
+        test_setup: TestSetup,
+    ) -> Result<()> {
+        let awaited_test_setup = test_setup.await;
+        let client = awaited_test_setup.db_client;
+        let extension_name = "watched";
+        client
+            .insert_one_into_extension_collection(
+                extension_name,
+                Document::from(json!({"word": "before"})),
+            )
+            .await?;
+
+        let changes = client
+            .watch_extension_collection(extension_name, "now")
+            .await?;
+        let mut changes = pin!(changes);
+        let inserted = client
+            .insert_one_into_extension_collection(
+                extension_name,
+                Document::from(json!({"word": "after"})),
+            )
+            .await?;
+
+        let change = changes.next().await.unwrap()?;
+        assert_eq!(json!(change.id), inserted.document["_id"]);
+        assert!(!change.deleted);
+        assert_eq!(change.document.unwrap().document["word"], json!("after"));
+
+        let replayed = client
+            .watch_extension_collection(extension_name, "0")
+            .await?;
+        let replayed: Vec<_> = replayed.take(2).collect().await;
+        assert_eq!(replayed.len(), 2);
+        Ok(())
+    }
 }
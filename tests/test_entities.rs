@@ -0,0 +1,646 @@
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use mlua::Lua;
+    use serde_json::{json, Value};
+    use std::fs;
+    use utilities::language::Language;
+    use wiktionary_en_entities::anki::{
+        escape, field, guid, unix_time_of_date, CardFields, CardTemplates, DEFAULT_BACK_TEMPLATE,
+        DEFAULT_FRONT_TEMPLATE,
+    };
+    use wiktionary_en_entities::dictionary_entry::{
+        AncestorRelation, Descendant, DictionaryEntry, Relation,
+    };
+    use wiktionary_en_entities::render::RenderFormat;
+    use wiktionary_en_entities::result::DictionaryResult;
+    use wiktionary_en_entities::validation::{ProblemKind, ValidationReport};
+    use wiktionary_en_lua::extension::load_lua_api;
+
+    fn test_entry() -> Result<DictionaryEntry> {
+        fs::read_to_string("./data/wiktionary-en-test.jsonl")?
+            .trim()
+            .parse()
+    }
+
+    fn related_words(entry: &DictionaryEntry, relation: Relation) -> Vec<String> {
+        entry
+            .related_words(relation)
+            .iter()
+            .map(|related_word| related_word.word.clone())
+            .collect()
+    }
+
+    #[test]
+    fn related_words_of_entry_and_senses() -> Result<()> {
+        let entry = test_entry()?;
+        let hypernyms = related_words(&entry, Relation::Hypernym);
+        assert!(hypernyms.contains(&"list".to_string()));
+        assert!(hypernyms.contains(&"wordbook".to_string()));
+        let coordinate_terms = related_words(&entry, Relation::CoordinateTerm);
+        let thesaurus_count = coordinate_terms
+            .iter()
+            .filter(|word| *word == "thesaurus")
+            .count();
+        assert_eq!(thesaurus_count, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn related_words_exclude_the_word_itself() -> Result<()> {
+        let entry = test_entry()?;
+        let synonyms = related_words(&entry, Relation::Synonym);
+        assert!(!synonyms.is_empty());
+        assert!(!synonyms.contains(&entry.word));
+        Ok(())
+    }
+
+    #[test]
+    fn sense_level_data() -> Result<()> {
+        let entry = test_entry()?;
+        let sense = &entry.senses[0];
+        assert_eq!(sense.wikidata, vec!["Q23622".to_string()]);
+        assert_eq!(sense.links[5].target_word(), "meaning");
+        assert!(sense.cross_references().contains(&"reference work"));
+        assert_eq!(entry.senses[1].raw_gloss_qualifiers(), vec!["by extension"]);
+        Ok(())
+    }
+
+    #[test]
+    fn selecting_a_sense() -> Result<()> {
+        let entry = test_entry()?;
+        let result = DictionaryResult {
+            word: entry.word.clone(),
+            did_you_mean: None,
+            hits: vec![entry.clone()],
+            remaining_hits: 0,
+            form_of: Vec::new(),
+            bookmark: None,
+        };
+        let with_sense = result.clone().with_sense(2);
+        assert_eq!(with_sense.hits[0].senses.len(), 1);
+        assert_eq!(with_sense.hits[0].senses[0].id, entry.senses[1].id);
+        assert!(result.with_sense(100).hits.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn banner_of_a_truncated_result() -> Result<()> {
+        colored::control::set_override(false);
+        let mut result = DictionaryResult {
+            word: "dictionary".to_string(),
+            did_you_mean: None,
+            hits: Vec::new(),
+            remaining_hits: 0,
+            form_of: Vec::new(),
+            bookmark: None,
+        };
+        assert_eq!(result.more_results_banner(), None);
+        result.remaining_hits = 2;
+        result.bookmark = Some("g1AAAA".to_string());
+        assert_eq!(
+            result.more_results_banner().unwrap(),
+            "2 more results, continue with --bookmark g1AAAA"
+        );
+        result.remaining_hits = 0;
+        assert!(result
+            .more_results_banner()
+            .unwrap()
+            .starts_with("More results may follow"));
+        Ok(())
+    }
+
+    #[test]
+    fn ancestry_from_etymology_templates() -> Result<()> {
+        let entry = test_entry()?;
+        let ancestry = entry.ancestry();
+        assert_eq!(ancestry.len(), 3);
+        assert_eq!(ancestry[0].relation, AncestorRelation::Inherited);
+        assert_eq!(ancestry[0].language, "Middle English");
+        assert_eq!(ancestry[0].language_code, "enm");
+        assert_eq!(ancestry[0].word.as_deref(), Some("dixionare"));
+        assert_eq!(ancestry[2].language, "Latin");
+        Ok(())
+    }
+
+    #[test]
+    fn descendants_tell_language_and_words() -> Result<()> {
+        let descendants: Vec<Descendant> = serde_json::from_value(json!([
+            {
+                "depth": 1,
+                "templates": [{
+                    "name": "desc",
+                    "args": {"1": "ja", "2": "ディクショナリー", "bor": "1"},
+                    "expansion": "→ Japanese: ディクショナリー (dikushonarī)"
+                }],
+                "text": "→ Japanese: ディクショナリー (dikushonarī)"
+            },
+            {
+                "depth": 2,
+                "templates": [
+                    {"name": "l", "args": {"1": "sv", "2": "diktionär"}},
+                    {"name": "l", "args": {"1": "sv", "2": "dikt"}}
+                ],
+                "text": "Swedish: diktionär, dikt"
+            },
+            {"depth": 1, "text": "Romance:"}
+        ]))?;
+        assert_eq!(descendants[0].language(), Some("Japanese"));
+        assert_eq!(descendants[0].language_code(), Some("ja"));
+        assert_eq!(descendants[0].words(), vec!["ディクショナリー"]);
+        assert!(descendants[0].is_borrowing());
+        assert_eq!(descendants[1].language(), Some("Swedish"));
+        assert_eq!(descendants[1].language_code(), Some("sv"));
+        assert_eq!(descendants[1].words(), vec!["diktionär", "dikt"]);
+        assert!(!descendants[1].is_borrowing());
+        assert_eq!(descendants[2].language(), Some("Romance"));
+        assert!(descendants[2].words().is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn syllables_of_hyphenations() -> Result<()> {
+        let entry = test_entry()?;
+        assert_eq!(entry.syllables(), ["dic", "tion", "a", "ry"]);
+        assert_eq!(entry.hyphenations[1].to_string(), "dic‧tion‧ary");
+        assert!(entry.has_syllable_count(4));
+        assert!(entry.has_syllable_count(3));
+        assert!(!entry.has_syllable_count(2));
+        let result = DictionaryResult {
+            word: entry.word.clone(),
+            did_you_mean: None,
+            hits: vec![entry],
+            remaining_hits: 0,
+            form_of: Vec::new(),
+            bookmark: None,
+        };
+        assert_eq!(result.clone().with_syllable_count(3).hits.len(), 1);
+        assert!(result.with_syllable_count(2).hits.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn accents_of_recordings() -> Result<()> {
+        let entry = test_entry()?;
+        let recordings: Vec<_> = entry
+            .sounds
+            .iter()
+            .filter(|sound| sound.audio.is_some())
+            .collect();
+        assert_eq!(recordings.len(), 2);
+        assert_eq!(recordings[0].accents(), vec!["uk".to_string()]);
+        assert!(recordings[1].has_accent("US"));
+        assert!(recordings[1]
+            .ogg_url
+            .as_ref()
+            .is_some_and(|url| url.ends_with("En-us-dictionary.ogg")));
+        let ipa = entry.sounds.iter().find(|sound| sound.ipa.is_some());
+        assert!(ipa.is_some_and(|sound| sound.has_accent("received-pronunciation")));
+        Ok(())
+    }
+
+    #[test]
+    fn senses_are_numbered_from_one() -> Result<()> {
+        colored::control::set_override(false);
+        let pretty = test_entry()?.to_pretty_string();
+        assert!(pretty.contains("\n1. "));
+        assert!(pretty.contains("\n2. (by extension)"));
+        Ok(())
+    }
+
+    #[test]
+    fn render_entry_as_markdown() -> Result<()> {
+        let mut entry = test_entry()?;
+        entry.etymology_text = Some("From *dictiōnārium*\nBy surface analysis".to_string());
+        let markdown = entry.render("markdown".parse()?);
+        assert!(markdown.starts_with("## dictionary (noun)\n"));
+        assert!(markdown.contains("\n### Senses\n\n1. A reference work"));
+        assert!(markdown.contains("\n2. *(by extension)* A reference work"));
+        assert!(markdown.contains("\n   - *a law dictionary*\n"));
+        assert!(markdown.contains("From \\*dictiōnārium\\*\n\nBy surface analysis"));
+        assert!(markdown.contains("[lexicon](https://en.wiktionary.org/wiki/lexicon)"));
+        assert!(markdown.contains(
+            "[explanatory dictionary](https://en.wiktionary.org/wiki/explanatory_dictionary)"
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn render_entry_as_html() -> Result<()> {
+        let mut entry = test_entry()?;
+        entry.senses[0].glosses = vec!["<b>bold</b> & more".to_string()];
+        let html = entry.render(RenderFormat::Html);
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<title>dictionary</title>"));
+        assert!(html.contains("<h3>Senses</h3>\n<ol>\n<li>&lt;b&gt;bold&lt;/b&gt; &amp; more"));
+        assert!(html.contains("<a href=\"https://en.wiktionary.org/wiki/lexicon\">lexicon</a>"));
+        assert!(html.trim_end().ends_with("</html>"));
+        assert!("pdf".parse::<RenderFormat>().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn anki_dates_are_days_since_the_epoch() -> Result<()> {
+        assert_eq!(unix_time_of_date("1970-01-01")?, 0);
+        assert_eq!(unix_time_of_date("2000-03-01")?, 951868800);
+        assert_eq!(unix_time_of_date("2024-12-31")?, 1735603200);
+        assert_eq!(unix_time_of_date("1969-12-31")?, -86400);
+        for date in ["2024-13-01", "2024-12-32", "2024-12", "yesterday", ""] {
+            assert!(unix_time_of_date(date).is_err(), "{}", date);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn anki_guids_are_stable() {
+        assert_eq!(
+            guid(Language::EN, "dictionary"),
+            "wiktionary-debfe8d0bf659094"
+        );
+        assert_eq!(
+            guid(Language::EN, "dictionary"),
+            guid(Language::EN, "dictionary")
+        );
+        assert_ne!(
+            guid(Language::EN, "dictionary"),
+            guid(Language::DE, "dictionary")
+        );
+        assert_ne!(
+            guid(Language::EN, "dictionary"),
+            guid(Language::EN, "lexicon")
+        );
+    }
+
+    #[test]
+    fn anki_templates_only_have_known_placeholders() -> Result<()> {
+        CardTemplates::init(
+            DEFAULT_FRONT_TEMPLATE.to_string(),
+            DEFAULT_BACK_TEMPLATE.to_string(),
+        )?;
+        CardTemplates::init("{word} ({count})".to_string(), "{pos} {".to_string())?;
+        let err = CardTemplates::init("{word}".to_string(), "{gloss}".to_string())
+            .err()
+            .map(|err| err.to_string());
+        assert!(err.is_some_and(|err| err.starts_with("unknown placeholder '{gloss}'")));
+        Ok(())
+    }
+
+    #[test]
+    fn anki_fields_are_escaped() -> Result<()> {
+        assert_eq!(escape("<b>a</b> & b"), "&lt;b&gt;a&lt;/b&gt; &amp; b");
+        assert_eq!(field("a\tb\nc"), "a b<br>c");
+        let mut entry = test_entry()?;
+        entry.senses[0].glosses = vec!["<b>bold</b>\tand\nmore".to_string()];
+        let fields = CardFields::of("dictionary", 3, &[entry]);
+        assert_eq!(fields.fill("{word} {count}"), "dictionary 3");
+        let back = field(&fields.fill("{glosses}"));
+        assert!(back.starts_with("<ol><li>&lt;b&gt;bold&lt;/b&gt; and<br>more</li>"));
+        assert!(!back.contains('\t'));
+        Ok(())
+    }
+
+    #[test]
+    fn anki_placeholders_are_filled_once() -> Result<()> {
+        let mut entry = test_entry()?;
+        entry.senses[0].glosses = vec!["see {examples} and {word}".to_string()];
+        entry.senses[0].examples.clear();
+        let fields = CardFields::of("{glosses}", 1, &[entry]);
+        let back = fields.fill("{word}|{glosses}|{unknown}|{");
+        assert!(back.starts_with("{glosses}|<ol><li>see {examples} and {word}</li>"));
+        assert!(back.ends_with("|{unknown}|{"));
+        Ok(())
+    }
+
+    #[test]
+    fn unmodelled_fields_survive_a_round_trip() -> Result<()> {
+        let line = fs::read_to_string("./data/wiktionary-en-test.jsonl")?;
+        let original: Value = serde_json::from_str(&line)?;
+        let entry = test_entry()?;
+        assert!(entry.extra.contains_key("head_templates"));
+        assert!(!entry.extra.contains_key("word"));
+        let round_trip: Value = serde_json::to_value(&entry)?;
+        assert_eq!(round_trip["head_templates"], original["head_templates"]);
+        assert_eq!(round_trip["lang"], original["lang"]);
+        Ok(())
+    }
+
+    #[test]
+    fn nested_unmodelled_fields_survive_a_round_trip() -> Result<()> {
+        let line = fs::read_to_string("./data/wiktionary-en-test.jsonl")?;
+        let mut original: Value = serde_json::from_str(&line)?;
+        original["senses"][0]["topics"] = json!(["lexicography"]);
+        original["senses"][0]["form_of"] = json!([{ "word": "dictionaries" }]);
+        original["senses"][0]["alt_of"] = json!([{ "word": "dictionnary" }]);
+        let entry: DictionaryEntry = original.to_string().parse()?;
+        let unmodelled = entry.unmodelled_fields();
+        for field in [
+            "senses.topics",
+            "senses.form_of",
+            "senses.alt_of",
+            "translations.roman",
+        ] {
+            assert!(unmodelled.contains(field), "{}", field);
+        }
+        let round_trip: Value = serde_json::to_value(&entry)?;
+        for field in ["topics", "form_of", "alt_of", "attestations"] {
+            assert_eq!(round_trip["senses"][0][field], original["senses"][0][field]);
+        }
+        assert_eq!(
+            round_trip["translations"][0]["roman"],
+            original["translations"][0]["roman"]
+        );
+        let report = ValidationReport::validate(std::io::Cursor::new(original.to_string()))?;
+        assert_eq!(report.unmodelled_fields.get("senses.topics"), Some(&1));
+        Ok(())
+    }
+
+    #[test]
+    fn category_names_of_entry_and_senses() -> mlua::Result<()> {
+        let entry = test_entry().expect("test data can be parsed");
+        let names = entry.category_names();
+        assert!(names.contains(&"Computing"));
+        assert!(names.windows(2).all(|pair| pair[0] < pair[1]));
+        let lua = Lua::new();
+        lua.globals().set("entry", entry.clone())?;
+        let first_category: String = lua
+            .load("return entry.senses[1].categories[1].name")
+            .eval()?;
+        assert!(names.contains(&first_category.as_str()));
+        load_lua_api(&lua)?;
+        let from_api: Vec<String> = lua
+            .load(r#"return require("wiktionary_api").categories(entry)"#)
+            .eval()?;
+        assert_eq!(from_api, names);
+        Ok(())
+    }
+
+    #[test]
+    fn validate_extract() -> Result<()> {
+        let line = fs::read_to_string("./data/wiktionary-en-test.jsonl")?;
+        let line = line.trim();
+        let mut without_senses: Value = serde_json::from_str(line)?;
+        without_senses["senses"] = Value::Array(Vec::new());
+        let without_senses = without_senses.to_string();
+        let extract = [line, line, "{\"word\": ", "", without_senses.as_str()].join("\n");
+        let report = ValidationReport::validate(std::io::Cursor::new(extract))?;
+        assert_eq!(report.lines, 4);
+        assert_eq!(report.problems.get(&ProblemKind::ParseFailure), Some(&1));
+        assert_eq!(report.problems.get(&ProblemKind::Duplicate), Some(&1));
+        assert_eq!(report.problems.get(&ProblemKind::EmptySenses), Some(&1));
+        let parse_failure = report
+            .examples
+            .iter()
+            .find(|example| example.kind == ProblemKind::ParseFailure)
+            .map(|example| example.line);
+        assert_eq!(parse_failure, Some(3));
+        assert_eq!(report.failed_lines, 2);
+        assert_eq!(report.error_rate(), 50.0);
+        assert_eq!(report.field_coverage.get("word"), Some(&3));
+        assert_eq!(report.field_coverage.get("senses"), Some(&2));
+        assert_eq!(report.unmodelled_fields.get("head_templates"), Some(&3));
+        assert!(!report.unmodelled_fields.contains_key("word"));
+        Ok(())
+    }
+
+    #[test]
+    fn lines_with_several_errors_fail_once() -> Result<()> {
+        let line = fs::read_to_string("./data/wiktionary-en-test.jsonl")?;
+        let mut without_word_and_senses: Value = serde_json::from_str(line.trim())?;
+        without_word_and_senses["word"] = json!("");
+        without_word_and_senses["senses"] = Value::Array(Vec::new());
+        let report =
+            ValidationReport::validate(std::io::Cursor::new(without_word_and_senses.to_string()))?;
+        assert_eq!(report.problems.get(&ProblemKind::EmptyWord), Some(&1));
+        assert_eq!(report.problems.get(&ProblemKind::EmptySenses), Some(&1));
+        assert_eq!(report.failed_lines, 1);
+        assert_eq!(report.error_rate(), 100.0);
+        Ok(())
+    }
+
+    #[test]
+    fn unmodelled_fields_are_nested_tables_in_lua() -> mlua::Result<()> {
+        let entry = test_entry().expect("test data can be parsed");
+        let lua = Lua::new();
+        lua.globals().set("entry", entry.clone())?;
+        let head_template: String = lua
+            .load("return entry.extra.head_templates[1].name")
+            .eval()?;
+        assert_eq!(head_template, "en-noun");
+        let from_lua: DictionaryEntry = lua.globals().get("entry")?;
+        assert_eq!(from_lua.extra, entry.extra);
+        Ok(())
+    }
+
+    #[test]
+    fn links_keep_their_fields_in_lua() -> mlua::Result<()> {
+        let entry = test_entry().expect("test data can be parsed");
+        let link = entry
+            .senses
+            .iter()
+            .flat_map(|sense| &sense.links)
+            .next()
+            .expect("test data has links")
+            .clone();
+        let lua = Lua::new();
+        lua.globals().set("entry", entry)?;
+        let (text, target_word): (String, String) = lua
+            .load(
+                r#"
+                local link
+                for _, sense in ipairs(entry.senses) do
+                  link = link or (sense.links or {})[1]
+                end
+                return link.text, link.target_word
+                "#,
+            )
+            .eval()?;
+        assert_eq!(text, link.text);
+        assert_eq!(target_word, link.target_word());
+        let from_lua: DictionaryEntry = lua
+            .load(
+                r#"
+                for _, sense in ipairs(entry.senses) do
+                  for _, link in ipairs(sense.links or {}) do link.text = "changed" end
+                end
+                return entry
+                "#,
+            )
+            .eval()?;
+        assert!(from_lua
+            .senses
+            .iter()
+            .flat_map(|sense| &sense.links)
+            .all(|link| link.text == "changed"));
+        Ok(())
+    }
+
+    #[test]
+    fn unmodelled_fields_are_tables_in_lua() -> mlua::Result<()> {
+        let entry = test_entry().expect("test data can be parsed");
+        let lua = Lua::new();
+        lua.globals().set("entry", entry.clone())?;
+        let head_template: String = lua.load("return entry.head_templates[1].name").eval()?;
+        assert_eq!(head_template, "en-noun");
+        let from_lua: DictionaryEntry = lua.globals().get("entry")?;
+        assert_eq!(from_lua.extra, entry.extra);
+        Ok(())
+    }
+
+    #[test]
+    fn renamed_fields_are_redirected_in_lua() -> mlua::Result<()> {
+        let mut entry = test_entry().expect("test data can be parsed");
+        entry._id = "some-id".to_string();
+        entry._rev = "1-abc".to_string();
+        let lua = Lua::new();
+        lua.globals().set("entry", entry.clone())?;
+        let etymology: Option<String> = lua.load("return entry.etymology").eval()?;
+        assert_eq!(etymology, entry.etymology_text);
+        let from_lua: DictionaryEntry = lua
+            .load(r#"entry.etymology = "changed"; return entry"#)
+            .eval()?;
+        assert_eq!(from_lua.etymology_text.as_deref(), Some("changed"));
+        assert_eq!(from_lua._id, "some-id");
+        assert_eq!(from_lua._rev, "1-abc");
+        assert!(!from_lua.extra.contains_key("etymology"));
+        Ok(())
+    }
+
+    mod lua_round_trip {
+        use mlua::Lua;
+        use proptest::prelude::*;
+        use serde_json::{json, Map, Value};
+        use wiktionary_en_entities::dictionary_entry::DictionaryEntry;
+        use wiktionary_en_entities::result::{DictionaryResult, DidYouMean};
+
+        fn json_value() -> impl Strategy<Value = Value> {
+            let leaf = prop_oneof![
+                any::<bool>().prop_map(Value::from),
+                any::<i32>().prop_map(Value::from),
+                "[a-z ]{0,8}".prop_map(Value::from),
+            ];
+            leaf.prop_recursive(3, 16, 4, |inner| {
+                prop_oneof![
+                    prop::collection::vec(inner.clone(), 0..4).prop_map(Value::from),
+                    prop::collection::btree_map("[a-z]{1,6}", inner, 0..4)
+                        .prop_map(|map| Value::Object(map.into_iter().collect())),
+                ]
+            })
+        }
+
+        fn related_words() -> impl Strategy<Value = Value> {
+            prop::collection::vec(
+                (
+                    "[a-z]{1,8}",
+                    prop::collection::vec("[a-z]{1,6}", 0..3),
+                    prop::option::of("[a-z ]{1,10}"),
+                )
+                    .prop_map(
+                        |(word, tags, sense)| json!({"word": word, "tags": tags, "sense": sense}),
+                    ),
+                0..3,
+            )
+            .prop_map(Value::from)
+        }
+
+        fn sense() -> impl Strategy<Value = Value> {
+            (
+                prop::option::of("[a-z0-9-]{1,12}"),
+                prop::collection::vec("[a-z ]{1,20}", 0..3),
+                prop::collection::vec("[a-z]{1,6}", 0..3),
+                prop::collection::vec(("[a-z]{1,6}", "[a-z#]{1,8}"), 0..3),
+                related_words(),
+            )
+                .prop_map(|(id, glosses, tags, links, synonyms)| {
+                    let links: Vec<Vec<String>> = links
+                        .into_iter()
+                        .map(|(text, target)| vec![text, target])
+                        .collect();
+                    json!({
+                        "id": id,
+                        "glosses": glosses,
+                        "tags": tags,
+                        "links": links,
+                        "synonyms": synonyms,
+                    })
+                })
+        }
+
+        fn dictionary_entry() -> impl Strategy<Value = DictionaryEntry> {
+            (
+                ("[a-z]{0,8}", "[a-z]{0,8}", "[a-z]{1,10}", "[a-z]{1,6}"),
+                prop::collection::vec(sense(), 0..3),
+                prop::option::of("[a-z ]{0,30}"),
+                prop::option::of(0u32..5),
+                related_words(),
+                prop::collection::btree_map("extra_[a-z]{1,6}", json_value(), 0..3),
+            )
+                .prop_map(
+                    |(
+                        (id, rev, word, pos),
+                        senses,
+                        etymology_text,
+                        etymology_number,
+                        hypernyms,
+                        extra,
+                    )| {
+                        let mut entry = json!({
+                            "_id": id,
+                            "_rev": rev,
+                            "lang_code": "en",
+                            "word": word,
+                            "pos": pos,
+                            "senses": senses,
+                            "etymology_text": etymology_text,
+                            "etymology_number": etymology_number,
+                            "hypernyms": hypernyms,
+                        });
+                        let extra: Map<String, Value> = extra.into_iter().collect();
+                        entry.as_object_mut().unwrap().extend(extra);
+                        serde_json::from_value(entry).unwrap()
+                    },
+                )
+        }
+
+        fn through_lua<T: mlua::IntoLua + mlua::FromLua>(value: T) -> mlua::Result<T> {
+            let lua = Lua::new();
+            lua.globals().set("value", value)?;
+            lua.globals().get("value")
+        }
+
+        proptest! {
+            #[test]
+            fn dictionary_entries(entry in dictionary_entry()) {
+                let round_trip = through_lua(entry.clone()).unwrap();
+                prop_assert_eq!(
+                    serde_json::to_value(&round_trip).unwrap(),
+                    serde_json::to_value(&entry).unwrap()
+                );
+            }
+
+            #[test]
+            fn dictionary_results(
+                hits in prop::collection::vec(dictionary_entry(), 0..3),
+                word in "[a-z]{1,8}",
+                suggestion in prop::option::of("[a-z]{1,8}"),
+                remaining_hits in 0usize..100,
+            ) {
+                let result = DictionaryResult {
+                    did_you_mean: suggestion.map(|suggestion| DidYouMean {
+                        searched_for: word.clone(),
+                        suggestion,
+                    }),
+                    word,
+                    hits,
+                    remaining_hits,
+                    form_of: Vec::new(),
+                    bookmark: None,
+                };
+                let round_trip = through_lua(result.clone()).unwrap();
+                prop_assert_eq!(
+                    serde_json::to_value(&round_trip).unwrap(),
+                    serde_json::to_value(&result).unwrap()
+                );
+            }
+        }
+    }
+}
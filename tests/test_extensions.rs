@@ -75,6 +75,9 @@ mod tests {
                 hits: vec![dictionary_entry.clone()],
                 did_you_mean: None,
                 word: dictionary_entry.word,
+                remaining_hits: 0,
+                form_of: Vec::new(),
+                bookmark: None,
             };
             if !found_words.contains(&dictionary_result.word) {
                 extension_handler
@@ -184,6 +187,29 @@ mod tests {
         Ok(())
     }
 
+    #[rstest]
+    #[tokio::test]
+    async fn format_recent_history_entries(
+        #[from(test_setup)]
+        #[future]
+        test_setup: TestSetup,
+    ) -> Result<()> {
+        let awaited_test_setup = test_setup.await;
+        let extension_handler = awaited_test_setup.extension_handler;
+
+        let _: ExtensionResult<String> = extension_handler
+            .call_extension("history", &vec!["index".to_string()])
+            .await?;
+        intercept_dictionary_entries(&extension_handler).await?;
+        let extension_result: ExtensionResult<String> = extension_handler
+            .call_extension("history", &vec!["recent".to_string()])
+            .await?;
+
+        assert_eq!(extension_result.result.lines().count(), 20);
+
+        Ok(())
+    }
+
     #[rstest]
     #[tokio::test]
     async fn delete_history_entries(
@@ -308,4 +334,18 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn list_hooks_and_extensions_without_couchdb() -> Result<()> {
+        let db_client = DbClient::connect(language())?;
+        let extension_handler = ExtensionHandler::init(DbClientMutex::from(db_client)).await?;
+
+        assert_eq!(
+            extension_handler.hooks()?,
+            vec!["format_did_you_mean_banner", "format_entry", "intercept"]
+        );
+        assert_eq!(extension_handler.extensions()?, vec!["history"]);
+
+        Ok(())
+    }
 }
@@ -1,7 +1,16 @@
 #[cfg(test)]
 mod tests {
+    use colored::Color;
+    use mlua::Lua;
     use rstest::rstest;
-    use utilities::colored_string_utils::{format_integer, Join};
+    use std::time::Duration;
+    use utilities::color::ColorChoice;
+    use utilities::colored_string_utils::{
+        format_integer, horizontal_line, line_width, set_line_width, Join,
+    };
+    use utilities::retry::RetryPolicy;
+    use utilities::theme::{parse_color, Role, Theme};
+    use wiktionary_en_lua::extension::load_lua_api;
 
     #[rstest]
     fn joining_strings() -> () {
@@ -31,4 +40,167 @@ mod tests {
     fn formatting_ten_thousand() -> () {
         assert_eq!(format_integer(10000).to_ascii_lowercase(), "10,000");
     }
+
+    #[rstest]
+    #[tokio::test]
+    async fn retrying_async_until_success() -> () {
+        let retry_policy = RetryPolicy {
+            retries: 2,
+            backoff: Duration::ZERO,
+            ..RetryPolicy::default()
+        };
+        let mut attempts = 0;
+        let result = retry_policy
+            .retry_async(
+                |_| true,
+                || {
+                    attempts += 1;
+                    let attempt = attempts;
+                    async move {
+                        if attempt < 3 {
+                            anyhow::bail!("attempt {} failed", attempt)
+                        }
+                        Ok(attempt)
+                    }
+                },
+            )
+            .await;
+        assert_eq!(result.unwrap(), 3);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn giving_up_async_after_the_last_retry() -> () {
+        let retry_policy = RetryPolicy {
+            retries: 1,
+            backoff: Duration::ZERO,
+            ..RetryPolicy::default()
+        };
+        let mut attempts = 0;
+        let result: anyhow::Result<()> = retry_policy
+            .retry_async(
+                |_| true,
+                || {
+                    attempts += 1;
+                    let attempt = attempts;
+                    async move { anyhow::bail!("attempt {} failed", attempt) }
+                },
+            )
+            .await;
+        assert_eq!(result.unwrap_err().to_string(), "attempt 2 failed");
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn retrying_async_only_transient_errors() -> () {
+        let retry_policy = RetryPolicy {
+            retries: 5,
+            backoff: Duration::ZERO,
+            ..RetryPolicy::default()
+        };
+        let mut attempts = 0;
+        let result: anyhow::Result<()> = retry_policy
+            .retry_async(
+                |err| err.to_string() == "unavailable",
+                || {
+                    attempts += 1;
+                    let attempt = attempts;
+                    async move {
+                        if attempt < 2 {
+                            anyhow::bail!("unavailable")
+                        }
+                        anyhow::bail!("not found")
+                    }
+                },
+            )
+            .await;
+        assert_eq!(result.unwrap_err().to_string(), "not found");
+        assert_eq!(attempts, 2);
+    }
+
+    #[rstest]
+    fn doubling_the_backoff() -> () {
+        let retry_policy = RetryPolicy {
+            backoff: Duration::from_millis(100),
+            ..RetryPolicy::default()
+        };
+        assert_eq!(retry_policy.backoff_for(0), Duration::from_millis(100));
+        assert_eq!(retry_policy.backoff_for(2), Duration::from_millis(400));
+    }
+
+    #[rstest]
+    fn choosing_when_to_color() -> () {
+        assert_eq!("Never".parse::<ColorChoice>().unwrap(), ColorChoice::Never);
+        assert!("sometimes".parse::<ColorChoice>().is_err());
+        ColorChoice::Always.apply();
+        assert_eq!(format_integer(7).to_string(), "\u{1b}[33m7\u{1b}[0m");
+        ColorChoice::Never.apply();
+        assert_eq!(format_integer(7).to_string(), "7");
+        ColorChoice::Auto.apply();
+    }
+
+    #[rstest]
+    fn configuring_the_line_width() -> mlua::Result<()> {
+        set_line_width(100);
+        assert_eq!(line_width(), 100);
+        assert_eq!(horizontal_line().len(), 100);
+        let lua = Lua::new();
+        load_lua_api(&lua)?;
+        lua.load(r#"package.path = package.path .. ";../lua/?.lua""#)
+            .exec()?;
+        let constants: mlua::Table = lua.load(r#"return require("constants")"#).eval()?;
+        assert_eq!(constants.get::<usize>("max_line_width")?, 100);
+        set_line_width(60);
+        assert_eq!(constants.get::<usize>("max_line_width")?, 60);
+        assert!(constants.set("max_line_width", 80).is_err());
+        set_line_width(0);
+        Ok(())
+    }
+
+    #[rstest]
+    fn parsing_colors() -> () {
+        assert_eq!(parse_color("bright_red").unwrap(), Color::BrightRed);
+        assert_eq!(parse_color("208").unwrap(), Color::AnsiColor(208));
+        assert_eq!(
+            parse_color("#ff8700").unwrap(),
+            Color::TrueColor {
+                r: 0xff,
+                g: 0x87,
+                b: 0x00
+            }
+        );
+        assert!(parse_color("256").is_err());
+        assert!(parse_color("#ff87").is_err());
+        assert!(parse_color("mauve").is_err());
+    }
+
+    #[rstest]
+    fn the_default_theme_keeps_the_classic_colors() -> () {
+        let theme = Theme::default();
+        assert_eq!(theme.color(Role::Headword), Some(Color::Green));
+        assert_eq!(theme.color(Role::Pos), None);
+        assert_eq!(theme.color(Role::SectionTitle), None);
+        assert_eq!(theme.color(Role::DidYouMean), Some(Color::Yellow));
+        assert_eq!(theme.color(Role::NotFound), Some(Color::Red));
+    }
+
+    #[rstest]
+    fn changing_roles_of_a_preset() -> () {
+        let mut theme = Theme::preset("light").unwrap();
+        theme.set_color(Role::Pos, "208").unwrap();
+        theme.set_color(Role::Number, "none").unwrap();
+        assert_eq!(theme.color(Role::Pos), Some(Color::AnsiColor(208)));
+        assert_eq!(theme.color(Role::Number), None);
+        assert_eq!(
+            theme.color(Role::Headword),
+            Theme::light().color(Role::Headword)
+        );
+        theme.set_color(Role::NotFound, "bright_red").unwrap();
+        assert_eq!(theme.color(Role::NotFound), Some(Color::BrightRed));
+        assert_eq!(theme.color(Role::Form), Theme::light().color(Role::Form));
+        assert_eq!("not_found".parse::<Role>().unwrap(), Role::NotFound);
+        assert_eq!(Role::iterator().count(), 8);
+        assert!(Theme::preset("solarized").is_err());
+        assert!("keyword".parse::<Role>().is_err());
+    }
 }
@@ -1,3 +1,20 @@
 Config = {}
 -- set a default language
 Config.language = "en"
+-- seconds to wait for CouchDB and sonic
+Config.timeout = 10
+-- attempts after a failed backend call, delayed by retry_backoff seconds and doubled each time
+Config.retries = 2
+Config.retry_backoff = 0.25
+-- number of lookups kept in the local cache, 0 disables it
+Config.cache_size = 1000
+-- folder with recordings named like on Wikimedia Commons, used by the pronounce command
+-- Config.audio_dir = "/path/to/commons-audio"
+-- command playing a recording given as its last argument, otherwise mpv, ffplay, paplay or afplay
+-- Config.audio_player = "mpv --no-video"
+-- line width of the output, otherwise the width of the terminal
+-- Config.width = 100
+-- colors of the output: "dark" (default) or "light", or a preset with roles changed; roles are
+-- headword, pos, section_title, example_index, did_you_mean, number, form and not_found, colors are names like
+-- "bright_red", numbers of the 256 color palette like 208, truecolors like "#ff8700" or "none"
+-- Config.theme = { preset = "dark", headword = "#87d7ff", pos = 208 }